@@ -0,0 +1,45 @@
+//! Benchmarks the generator and each part of every day on its real input.
+//!
+//! Criterion keeps its estimates as JSON under `target/criterion/<year>_day<N>/<step>/`.
+//! To check a change for regressions, save a baseline first and compare against it:
+//!
+//! ```text
+//! cargo bench --bench days -- --save-baseline before
+//! cargo bench --bench days -- --baseline before
+//! ```
+//!
+//! Pass a filter such as `2022_day16/` to only run some of the days.
+
+use std::{fs, path::Path, time::Duration};
+
+use aoc2022::solutions::{self, YEARS};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn days(c: &mut Criterion) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    for &year in YEARS {
+        for day in solutions::days(year).unwrap() {
+            let path = root.join(format!("input/{year}/day{}.txt", day.day));
+            let Ok(input) = fs::read_to_string(&path) else {
+                eprintln!("no input at {}, skipping", path.display());
+                continue;
+            };
+            let input = input.trim_end_matches('\n');
+
+            let mut group = c.benchmark_group(format!("{year}_day{:02}", day.day));
+            // Some parts take tens of seconds per run, so keep the sample count at the minimum.
+            group
+                .sample_size(10)
+                .warm_up_time(Duration::from_millis(500));
+            group.bench_function("generator", |b| b.iter(|| (day.parse)(input).unwrap()));
+            let solvers = (day.parse)(input).unwrap();
+            for (name, solver) in ["part1", "part2"].into_iter().zip(&solvers) {
+                group.bench_function(name, |b| b.iter(solver));
+            }
+            group.finish();
+        }
+    }
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,136 @@
+//! Runs the solutions straight from their generator and solver functions,
+//! without going through `cargo aoc`.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use tracing_subscriber::EnvFilter;
+
+use aoc2022::solutions::{self, Day};
+
+#[derive(Parser)]
+#[command(about = "Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Log level (error, warn, info, debug or trace) or a full filter such as
+    /// `aoc2022::y2022::day16=trace`. Defaults to `$AOC_LOG`, then `warn`.
+    #[arg(long, global = true)]
+    log: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, or every day with `--all`.
+    Run(RunArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to solve.
+    #[arg(long, required_unless_present = "all")]
+    day: Option<u32>,
+    /// Only run this part; every part runs by default.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Solve every day that has an input file.
+    #[arg(long, conflicts_with_all = ["day", "input"])]
+    all: bool,
+    /// Read the puzzle input from here instead of `input/<year>/day<N>.txt`.
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Puzzle year, which also picks the input directory.
+    #[arg(long, default_value_t = 2022)]
+    year: u32,
+}
+
+fn default_input(year: u32, day: u32) -> PathBuf {
+    Path::new("input")
+        .join(year.to_string())
+        .join(format!("day{day}.txt"))
+}
+
+/// Solves `day` on the input at `path`, printing each answer with its timing.
+fn solve(day: &Day, path: &Path, part: Option<u8>) -> Result<()> {
+    let input = fs::read_to_string(path)
+        .with_context(|| format!("reading input for day {} from {}", day.day, path.display()))?;
+
+    let start = Instant::now();
+    let solvers = (day.parse)(input.trim_end_matches('\n'))?;
+    println!("Day {} (generator: {:?})", day.day, start.elapsed());
+
+    if let Some(part) = part.filter(|&part| usize::from(part) > solvers.len()) {
+        println!("  No part {part}");
+        return Ok(());
+    }
+
+    for (number, solver) in (1..).zip(&solvers) {
+        if part.is_some_and(|part| part != number) {
+            continue;
+        }
+        let start = Instant::now();
+        let answer = solver().with_context(|| format!("day {} part {number}", day.day))?;
+        println!("  Part {number} ({:?}): {answer}", start.elapsed());
+    }
+    Ok(())
+}
+
+fn run(args: RunArgs) -> Result<()> {
+    let Some(days) = solutions::days(args.year) else {
+        bail!("no solutions for {}", args.year);
+    };
+
+    if args.all {
+        let mut failed = 0;
+        for day in days {
+            let path = default_input(args.year, day.day);
+            if !path.exists() {
+                println!("Day {}: no input at {}, skipping", day.day, path.display());
+                continue;
+            }
+            if let Err(err) = solve(day, &path, args.part) {
+                eprintln!("Day {} failed: {err:#}", day.day);
+                failed += 1;
+            }
+        }
+        if failed > 0 {
+            bail!("{failed} day(s) failed");
+        }
+        return Ok(());
+    }
+
+    let number = args.day.expect("clap requires --day without --all");
+    let Some(day) = solutions::find_day(args.year, number) else {
+        bail!("no solution for {} day {number}", args.year);
+    };
+    let path = args
+        .input
+        .unwrap_or_else(|| default_input(args.year, number));
+    solve(day, &path, args.part)
+}
+
+/// Sends logs to stderr, so stdout only ever carries answers.
+fn init_logging(directive: Option<&str>) -> Result<()> {
+    let filter = match directive {
+        Some(directive) => EnvFilter::try_new(directive)?,
+        None => EnvFilter::try_from_env("AOC_LOG").unwrap_or_else(|_| EnvFilter::new("warn")),
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .init();
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    init_logging(cli.log.as_deref())?;
+    match cli.command {
+        Command::Run(args) => run(args),
+    }
+}
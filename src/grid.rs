@@ -0,0 +1,584 @@
+//! Two-dimensional grids shared by the map puzzles.
+//!
+//! [`Grid`] is a dense, row-major grid addressed by `(row, col)`, with
+//! rotated and transposed [`GridView`]s over it. [`SparseGrid`] holds cells at
+//! arbitrary `(x, y)` coordinates and grows as cells are inserted.
+
+use std::{
+    collections::HashMap,
+    ops::{Index, IndexMut},
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::parsing;
+
+/// Offsets to the four orthogonal neighbors, clockwise from up.
+///
+/// Offsets are `(d_row, d_col)` and rows grow downwards, so up is `(-1, 0)`.
+/// This is the opposite of [`crate::point`], where `y` grows upwards.
+pub const NEIGHBORS4: [(i64, i64); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Offsets to all eight neighbors, clockwise from up.
+pub const NEIGHBORS8: [(i64, i64); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            height,
+            width,
+            cells: vec![fill; height * width],
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        let mut cells = Vec::with_capacity(height * width);
+        for (r, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(anyhow!("row {r} has {} cells, expected {width}", row.len()));
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            height,
+            width,
+            cells,
+        })
+    }
+
+    /// Parses a character map, one row per line, converting each character with `f`.
+    pub fn parse(day: u32, input: &str, mut f: impl FnMut(char) -> Result<T>) -> Result<Grid<T>> {
+        let mut width = None;
+        let rows = parsing::lines(day, input)
+            .map(|line| {
+                let row =
+                    line.parse(|text| text.chars().map(&mut f).collect::<Result<Vec<_>>>())?;
+                match width {
+                    None => width = Some(row.len()),
+                    Some(width) if width != row.len() => {
+                        return Err(
+                            line.error(anyhow!("row has {} cells, expected {width}", row.len()))
+                        )
+                    }
+                    Some(_) => (),
+                }
+                Ok(row)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Grid::from_rows(rows)
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn in_bounds(&self, (row, col): (usize, usize)) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.in_bounds(pos)
+            .then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        if self.in_bounds(pos) {
+            Some(&mut self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// Every position in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            height: self.height,
+            width: self.width,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The in-bounds orthogonal neighbors of `pos`.
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBORS4
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    /// The in-bounds orthogonal and diagonal neighbors of `pos`.
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBORS8
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    /// `pos` moved by `(d_row, d_col)`, if that stays inside the grid.
+    pub fn offset(
+        &self,
+        (row, col): (usize, usize),
+        (d_row, d_col): (i64, i64),
+    ) -> Option<(usize, usize)> {
+        let row = usize::try_from(row as i64 + d_row).ok()?;
+        let col = usize::try_from(col as i64 + d_col).ok()?;
+        self.in_bounds((row, col)).then_some((row, col))
+    }
+
+    /// A view of the whole grid in its original orientation.
+    pub fn view(&self) -> GridView<'_, T> {
+        GridView {
+            grid: self,
+            height: self.height,
+            width: self.width,
+            transform: Transform::IDENTITY,
+        }
+    }
+
+    /// A view of the grid turned clockwise by `quarter_turns` quarter turns.
+    pub fn rotated(&self, quarter_turns: usize) -> GridView<'_, T> {
+        self.view().rotated(quarter_turns)
+    }
+
+    /// A view of the grid mirrored along its main diagonal.
+    pub fn transposed(&self) -> GridView<'_, T> {
+        self.view().transposed()
+    }
+
+    /// Renders the grid back to a character map, one line per row.
+    pub fn render(&self, f: impl FnMut(&T) -> char) -> String {
+        self.view().render(f)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+    fn index(&self, pos: (usize, usize)) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside a {}x{} grid", self.height, self.width))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        let (height, width) = (self.height, self.width);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside a {height}x{width} grid"))
+    }
+}
+
+/// Maps a view position `(r, c)` to `matrix * (r, c) + offset` in the underlying grid.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Transform {
+    matrix: [[i64; 2]; 2],
+    offset: (i64, i64),
+}
+
+impl Transform {
+    const IDENTITY: Transform = Transform {
+        matrix: [[1, 0], [0, 1]],
+        offset: (0, 0),
+    };
+
+    fn apply(&self, (r, c): (i64, i64)) -> (i64, i64) {
+        let [[a, b], [d, e]] = self.matrix;
+        (a * r + b * c + self.offset.0, d * r + e * c + self.offset.1)
+    }
+
+    /// The transform that applies `inner` first and then `self`.
+    fn compose(&self, inner: &Transform) -> Transform {
+        let [[a, b], [d, e]] = self.matrix;
+        let [[p, q], [s, t]] = inner.matrix;
+        Transform {
+            matrix: [
+                [a * p + b * s, a * q + b * t],
+                [d * p + e * s, d * q + e * t],
+            ],
+            offset: self.apply(inner.offset),
+        }
+    }
+}
+
+/// A read-only, reoriented window onto a [`Grid`].
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    height: usize,
+    width: usize,
+    transform: Transform,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The position in the underlying grid that `pos` in this view shows.
+    pub fn source_pos(&self, (row, col): (usize, usize)) -> (usize, usize) {
+        assert!(row < self.height && col < self.width);
+        let (row, col) = self.transform.apply((row as i64, col as i64));
+        (row as usize, col as usize)
+    }
+
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<&'a T> {
+        (row < self.height && col < self.width).then(|| &self.grid[self.source_pos((row, col))])
+    }
+
+    pub fn rotated(&self, quarter_turns: usize) -> GridView<'a, T> {
+        (0..quarter_turns % 4).fold(*self, |view, _| view.rotated_once())
+    }
+
+    fn rotated_once(&self) -> GridView<'a, T> {
+        // Turning clockwise, row r of the result is column r of this view read bottom to top.
+        let turn = Transform {
+            matrix: [[0, -1], [1, 0]],
+            offset: (self.height as i64 - 1, 0),
+        };
+        GridView {
+            grid: self.grid,
+            height: self.width,
+            width: self.height,
+            transform: self.transform.compose(&turn),
+        }
+    }
+
+    pub fn transposed(&self) -> GridView<'a, T> {
+        let flip = Transform {
+            matrix: [[0, 1], [1, 0]],
+            offset: (0, 0),
+        };
+        GridView {
+            grid: self.grid,
+            height: self.width,
+            width: self.height,
+            transform: self.transform.compose(&flip),
+        }
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// The cells of each row, in view order.
+    pub fn rows(self) -> impl Iterator<Item = impl Iterator<Item = &'a T>> {
+        (0..self.height)
+            .map(move |row| (0..self.width).map(move |col| &self.grid[self.source_pos((row, col))]))
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            height: self.height,
+            width: self.width,
+            cells: self
+                .positions()
+                .map(|pos| self.grid[self.source_pos(pos)].clone())
+                .collect(),
+        }
+    }
+
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.map(&mut f).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Cells at arbitrary `(x, y)` coordinates, with `y` growing downwards when rendered.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<(i64, i64), T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    /// Parses a character map with its top-left corner at `(0, 0)`; `f`
+    /// returns `None` for characters that should leave the cell empty.
+    pub fn parse(
+        day: u32,
+        input: &str,
+        mut f: impl FnMut(char) -> Result<Option<T>>,
+    ) -> Result<SparseGrid<T>> {
+        let mut grid = SparseGrid::new();
+        for line in parsing::lines(day, input) {
+            let y = line.number as i64 - 1;
+            line.parse(|text| {
+                for (x, c) in text.chars().enumerate() {
+                    if let Some(cell) = f(c)? {
+                        grid.insert((x as i64, y), cell);
+                    }
+                }
+                Ok(())
+            })?;
+        }
+        Ok(grid)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn insert(&mut self, pos: (i64, i64), cell: T) -> Option<T> {
+        self.cells.insert(pos, cell)
+    }
+
+    pub fn remove(&mut self, pos: (i64, i64)) -> Option<T> {
+        self.cells.remove(&pos)
+    }
+
+    pub fn get(&self, pos: (i64, i64)) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: (i64, i64)) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn contains(&self, pos: (i64, i64)) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.cells.keys().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((i64, i64), &T)> {
+        self.cells.iter().map(|(&pos, cell)| (pos, cell))
+    }
+
+    /// The smallest `((min_x, min_y), (max_x, max_y))` box holding every cell.
+    pub fn bounds(&self) -> Option<((i64, i64), (i64, i64))> {
+        let mut positions = self.positions();
+        let first = positions.next()?;
+        Some(positions.fold(
+            (first, first),
+            |((min_x, min_y), (max_x, max_y)), (x, y)| {
+                ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+            },
+        ))
+    }
+
+    /// The orthogonal neighbors of `pos`, whether or not they hold a cell.
+    pub fn neighbors4(pos: (i64, i64)) -> impl Iterator<Item = (i64, i64)> {
+        NEIGHBORS4
+            .into_iter()
+            .map(move |(dy, dx)| (pos.0 + dx, pos.1 + dy))
+    }
+
+    /// The orthogonal and diagonal neighbors of `pos`, whether or not they hold a cell.
+    pub fn neighbors8(pos: (i64, i64)) -> impl Iterator<Item = (i64, i64)> {
+        NEIGHBORS8
+            .into_iter()
+            .map(move |(dy, dx)| (pos.0 + dx, pos.1 + dy))
+    }
+
+    /// Renders the bounding box of the grid, one line per `y` from the top.
+    pub fn render(&self, mut f: impl FnMut(Option<&T>) -> char) -> String {
+        let Some(((min_x, min_y), (max_x, max_y))) = self.bounds() else {
+            return String::new();
+        };
+        (min_y..=max_y)
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| f(self.get((x, y))))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> FromIterator<((i64, i64), T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = ((i64, i64), T)>>(iter: I) -> Self {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<((i64, i64), T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = ((i64, i64), T)>>(&mut self, iter: I) {
+        self.cells.extend(iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(input: &str) -> Grid<char> {
+        Grid::parse(1, input, Ok).unwrap()
+    }
+
+    #[test]
+    fn rotations_and_transposition() {
+        let grid = chars("abc\ndef");
+        let turns = [
+            "abc\ndef",
+            "da\neb\nfc",
+            "fed\ncba",
+            "cf\nbe\nad",
+            "abc\ndef",
+        ];
+        for (quarter_turns, expected) in turns.into_iter().enumerate() {
+            let view = grid.rotated(quarter_turns);
+            assert_eq!(view.render(|&c| c), expected, "{quarter_turns} turns");
+            assert_eq!(view.to_grid(), chars(expected), "{quarter_turns} turns");
+        }
+        let transposed = grid.transposed();
+        assert_eq!((transposed.height(), transposed.width()), (3, 2));
+        assert_eq!(transposed.render(|&c| c), "ad\nbe\ncf");
+        assert_eq!(transposed.get((2, 0)), Some(&'c'));
+        assert_eq!(transposed.get((0, 2)), None);
+        assert_eq!(transposed.transposed().to_grid(), grid);
+        // Rotating a transposed view composes the two transforms.
+        assert_eq!(transposed.rotated(1).render(|&c| c), "cba\nfed");
+    }
+
+    #[test]
+    fn parse_and_render_round_trip() {
+        let map = "#..#\n.##.\n...#";
+        let grid = Grid::parse(1, map, |c| Ok(c == '#')).unwrap();
+        assert_eq!((grid.height(), grid.width()), (3, 4));
+        assert!(grid[(1, 2)] && !grid[(2, 0)]);
+        assert_eq!(grid.render(|&wall| if wall { '#' } else { '.' }), map);
+
+        let sparse = SparseGrid::parse(1, map, |c| Ok((c == '#').then_some(()))).unwrap();
+        assert_eq!(sparse.len(), 5);
+        assert!(sparse.contains((3, 2)) && !sparse.contains((2, 2)));
+        assert_eq!(sparse.bounds(), Some(((0, 0), (3, 2))));
+        assert_eq!(
+            sparse.render(|cell| if cell.is_some() { '#' } else { '.' }),
+            map
+        );
+    }
+
+    #[test]
+    fn sparse_bounds_and_render() {
+        let mut sparse = SparseGrid::new();
+        assert_eq!(sparse.bounds(), None);
+        assert_eq!(sparse.render(|_| '#'), "");
+        sparse.extend([((-2, 1), 'a'), ((1, -1), 'b')]);
+        assert_eq!(sparse.bounds(), Some(((-2, -1), (1, 1))));
+        assert_eq!(
+            sparse.render(|cell| cell.copied().unwrap_or('.')),
+            "...b\n....\na..."
+        );
+        sparse.remove((1, -1));
+        assert_eq!(sparse.bounds(), Some(((-2, 1), (-2, 1))));
+    }
+
+    #[test]
+    fn ragged_rows_are_rejected() {
+        let err = Grid::from_rows(vec![vec![1, 2], vec![3]]).unwrap_err();
+        assert_eq!(err.to_string(), "row 1 has 1 cells, expected 2");
+        let err = Grid::parse(1, "ab\nab\nc", Ok).unwrap_err();
+        assert!(err.to_string().starts_with("day 1, line 3"), "{err}");
+    }
+
+    #[test]
+    fn neighbors_go_clockwise_from_up() {
+        let grid = Grid::new(3, 3, ());
+        assert_eq!(
+            grid.neighbors8((1, 1)).collect::<Vec<_>>(),
+            [
+                (0, 1),
+                (0, 2),
+                (1, 2),
+                (2, 2),
+                (2, 1),
+                (2, 0),
+                (1, 0),
+                (0, 0)
+            ]
+        );
+        assert_eq!(
+            grid.neighbors8((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbors4((2, 1)).collect::<Vec<_>>(),
+            [(1, 1), (2, 2), (2, 0)]
+        );
+        assert_eq!(
+            SparseGrid::<()>::neighbors8((0, 0)).collect::<Vec<_>>(),
+            [
+                (0, -1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0),
+                (-1, -1)
+            ]
+        );
+    }
+}
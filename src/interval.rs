@@ -0,0 +1,285 @@
+//! Sets of integers stored as sorted, disjoint inclusive ranges.
+//!
+//! [`IntervalSet`] keeps its ranges in a `BTreeMap` from start to end, merging
+//! overlapping and adjacent ranges as they are inserted, so lookups take
+//! `O(log n)` and inserting or removing a range takes `O(log n)` plus the
+//! number of ranges it merges or splits. The set operations insert or remove
+//! each range of the other set in turn.
+
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use num::PrimInt;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct IntervalSet<T> {
+    /// Start to end of each range. No two ranges overlap or touch.
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T: PrimInt> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet::new()
+    }
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet {
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    /// The stored ranges, in order, whose start is at most `hi` and whose end
+    /// is at least `lo`, last first.
+    fn touching(&self, lo: T, hi: T) -> impl Iterator<Item = (T, T)> + '_ {
+        self.ranges
+            .range(..=hi)
+            .rev()
+            .map(|(&start, &end)| (start, end))
+            .take_while(move |&(_, end)| end >= lo)
+    }
+
+    /// Adds every value in `range`. Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut lo, mut hi) = range.into_inner();
+        if lo > hi {
+            return;
+        }
+        // Widen by one on each side so that adjacent ranges merge too.
+        let merged: Vec<(T, T)> = self
+            .touching(lo.saturating_sub(T::one()), hi.saturating_add(T::one()))
+            .collect();
+        for (start, end) in merged {
+            self.ranges.remove(&start);
+            lo = lo.min(start);
+            hi = hi.max(end);
+        }
+        self.ranges.insert(lo, hi);
+    }
+
+    /// Removes every value in `range`, splitting the ranges it cuts into.
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (lo, hi) = range.into_inner();
+        if lo > hi {
+            return;
+        }
+        let cut: Vec<(T, T)> = self.touching(lo, hi).collect();
+        for (start, end) in cut {
+            self.ranges.remove(&start);
+            if start < lo {
+                self.ranges.insert(start, lo - T::one());
+            }
+            if end > hi {
+                self.ranges.insert(hi + T::one(), end);
+            }
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.touching(value, value).next().is_some()
+    }
+
+    /// Whether every value in `range` is in the set. Empty ranges always are.
+    pub fn contains_range(&self, range: RangeInclusive<T>) -> bool {
+        let (lo, hi) = range.into_inner();
+        lo > hi
+            || self
+                .touching(lo, lo)
+                .next()
+                .is_some_and(|(_, end)| end >= hi)
+    }
+
+    /// Whether any value in `range` is in the set.
+    pub fn overlaps(&self, range: RangeInclusive<T>) -> bool {
+        let (lo, hi) = range.into_inner();
+        lo <= hi && self.touching(lo, hi).next().is_some()
+    }
+
+    /// Takes `O(m log(n + m))` for `m` ranges in `other`, plus a copy of `self`.
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut union = self.clone();
+        union.extend(other.iter());
+        union
+    }
+
+    /// Takes `O(m log n + k)` for `m` ranges in `other` and `k` ranges in the result.
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intersection = IntervalSet::new();
+        for range in other.iter() {
+            let (lo, hi) = range.into_inner();
+            for (start, end) in self.touching(lo, hi) {
+                intersection.insert(start.max(lo)..=end.min(hi));
+            }
+        }
+        intersection
+    }
+
+    /// Takes `O(m log n)` for `m` ranges in `other`, plus a copy of `self`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// How many values the set holds, or `None` if that doesn't fit in a
+    /// `u128`, as for every `u128` or every `i128`.
+    pub fn len(&self) -> Option<u128> {
+        self.ranges.iter().try_fold(0u128, |len, (&start, &end)| {
+            len.checked_add(count(start, end)?)
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    /// The smallest and largest values in the set.
+    pub fn bounds(&self) -> Option<RangeInclusive<T>> {
+        let (&lo, _) = self.ranges.first_key_value()?;
+        let (_, &hi) = self.ranges.last_key_value()?;
+        Some(lo..=hi)
+    }
+
+    /// The disjoint ranges making up the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..=end)
+    }
+
+    /// The missing ranges between the smallest and largest values, in
+    /// ascending order.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges
+            .iter()
+            .zip(self.ranges.keys().skip(1))
+            .map(|((_, &end), &next)| end + T::one()..=next - T::one())
+    }
+}
+
+/// How many values `start..=end` holds, or `None` if that doesn't fit in a `u128`.
+fn count<T: PrimInt>(start: T, end: T) -> Option<u128> {
+    if start >= T::zero() || end < T::zero() {
+        (end - start).to_u128()?.checked_add(1)
+    } else {
+        // Split the range at zero so that neither side overflows `T`.
+        let below = (T::zero() - (start + T::one())).to_u128()? + 1;
+        let above = end.to_u128()? + 1;
+        below.checked_add(above)
+    }
+}
+
+impl<T: PrimInt> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: PrimInt> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: PrimInt> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        IntervalSet::from_iter([range])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<RangeInclusive<i32>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn insert_merges_overlapping_and_adjacent_ranges() {
+        let mut set = IntervalSet::from_iter([1..=3, 7..=9, 20..=25]);
+        set.insert(4..=5);
+        assert_eq!(ranges(&set), [1..=5, 7..=9, 20..=25]);
+        set.insert(6..=21);
+        assert_eq!(ranges(&set), [1..=25]);
+        // An empty range adds nothing.
+        let (hi, lo) = (2, 10);
+        set.insert(lo..=hi);
+        assert_eq!(ranges(&set), [1..=25]);
+        assert!(set.contains(1) && set.contains(25) && !set.contains(26));
+        assert!(set.contains_range(3..=20) && !set.contains_range(0..=20));
+    }
+
+    #[test]
+    fn remove_cuts_ranges() {
+        let mut set = IntervalSet::from(0..=20);
+        set.remove(5..=7);
+        assert_eq!(ranges(&set), [0..=4, 8..=20]);
+        set.remove(-3..=1);
+        assert_eq!(ranges(&set), [2..=4, 8..=20]);
+        set.remove(18..=30);
+        assert_eq!(ranges(&set), [2..=4, 8..=17]);
+        set.remove(4..=8);
+        assert_eq!(ranges(&set), [2..=3, 9..=17]);
+        set.remove(0..=100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = IntervalSet::from_iter([0..=4, 10..=14, 20..=24]);
+        let b = IntervalSet::from_iter([3..=11, 14..=21, 30..=31]);
+        assert_eq!(ranges(&a.union(&b)), [0..=24, 30..=31]);
+        assert_eq!(
+            ranges(&a.intersection(&b)),
+            [3..=4, 10..=11, 14..=14, 20..=21]
+        );
+        assert_eq!(ranges(&a.difference(&b)), [0..=2, 12..=13, 22..=24]);
+        assert_eq!(ranges(&b.difference(&a)), [5..=9, 15..=19, 30..=31]);
+        assert!(a.intersection(&IntervalSet::new()).is_empty());
+    }
+
+    #[test]
+    fn gaps_bounds_and_len() {
+        let set = IntervalSet::from_iter([0..=4, 10..=14, 15..=15, 20..=24]);
+        assert_eq!(set.gaps().collect::<Vec<_>>(), [5..=9, 16..=19]);
+        assert_eq!(set.bounds(), Some(0..=24));
+        assert_eq!(set.len(), Some(16));
+        assert_eq!(IntervalSet::<i32>::new().bounds(), None);
+
+        let mut full = IntervalSet::from(i32::MIN..=i32::MAX);
+        assert_eq!(full.len(), Some(1 << 32));
+        full.remove(-1..=0);
+        assert_eq!(ranges(&full), [i32::MIN..=-2, 1..=i32::MAX]);
+        assert_eq!(full.gaps().collect::<Vec<_>>(), [-1..=0]);
+        assert_eq!(full.len(), Some((1 << 32) - 2));
+        full.insert(i32::MIN..=i32::MAX);
+        assert_eq!(ranges(&full), [i32::MIN..=i32::MAX]);
+    }
+
+    #[test]
+    fn len_at_the_type_limits() {
+        assert_eq!(IntervalSet::from(0..=u8::MAX).len(), Some(256));
+        assert_eq!(IntervalSet::from(i64::MIN..=-1).len(), Some(1 << 63));
+        assert_eq!(IntervalSet::from(i64::MIN..=i64::MAX).len(), Some(1 << 64));
+        assert_eq!(IntervalSet::from(0..=u64::MAX).len(), Some(1 << 64));
+        assert_eq!(IntervalSet::from(1..=u128::MAX).len(), Some(u128::MAX));
+        assert_eq!(IntervalSet::from(0..=u128::MAX).len(), None);
+        assert_eq!(IntervalSet::from(i128::MIN..=i128::MAX).len(), None);
+        assert_eq!(
+            IntervalSet::from(i128::MIN..=i128::MAX - 1).len(),
+            Some(u128::MAX)
+        );
+        let halves = IntervalSet::from_iter([0..=u128::MAX / 2, u128::MAX / 2 + 2..=u128::MAX]);
+        assert_eq!(halves.len(), Some(u128::MAX));
+        assert_eq!(IntervalSet::<u128>::new().len(), Some(0));
+    }
+}
@@ -0,0 +1,116 @@
+//! Helpers shared by the `#[aoc_generator]` functions.
+//!
+//! Every generator returns `anyhow::Result`, and failures are reported as a
+//! [`ParseError`] naming the day, the 1-based line number and the offending
+//! text, so a bad input file points straight at the line that broke it.
+
+use std::{error::Error, fmt, str::FromStr};
+
+use anyhow::{anyhow, Context, Result};
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub day: u32,
+    pub line: usize,
+    pub text: String,
+    pub cause: anyhow::Error,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ParseError {
+            day,
+            line,
+            text,
+            cause,
+        } = self;
+        write!(f, "day {day}, line {line} ({text:?}): {cause:#}")
+    }
+}
+
+impl Error for ParseError {}
+
+/// One line of puzzle input, numbered from 1.
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    pub day: u32,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Runs `f` over the text of this line, tagging any failure with its position.
+    pub fn parse<T>(self, f: impl FnOnce(&'a str) -> Result<T>) -> Result<T> {
+        f(self.text).map_err(|cause| self.error(cause))
+    }
+
+    pub fn error(self, cause: impl Into<anyhow::Error>) -> anyhow::Error {
+        ParseError {
+            day: self.day,
+            line: self.number,
+            text: self.text.to_owned(),
+            cause: cause.into(),
+        }
+        .into()
+    }
+}
+
+/// Splits `input` into numbered lines. Trailing blank lines are dropped, and
+/// CRLF line endings are handled by `str::lines`.
+pub fn lines(day: u32, input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .trim_end()
+        .lines()
+        .enumerate()
+        .map(move |(i, text)| Line {
+            day,
+            number: i + 1,
+            text,
+        })
+}
+
+/// Parses every line of `input` with `f`.
+pub fn parse_lines<T>(
+    day: u32,
+    input: &str,
+    mut f: impl FnMut(&str) -> Result<T>,
+) -> Result<Vec<T>> {
+    lines(day, input).map(|line| line.parse(&mut f)).collect()
+}
+
+/// The only line of a single-line input such as a datastream or jet pattern.
+pub fn single_line(day: u32, input: &str) -> Result<Line<'_>> {
+    let mut lines = lines(day, input);
+    let line = lines
+        .next()
+        .ok_or_else(|| anyhow!("day {day}: input is empty"))?;
+    match lines.next() {
+        None => Ok(line),
+        Some(extra) => Err(extra.error(anyhow!("expected a single line of input"))),
+    }
+}
+
+pub fn split_once<'a>(s: &'a str, delimiter: &str) -> Result<(&'a str, &'a str)> {
+    s.split_once(delimiter)
+        .ok_or_else(|| anyhow!("expected {delimiter:?} in {s:?}"))
+}
+
+pub fn strip_prefix<'a>(s: &'a str, prefix: &str) -> Result<&'a str> {
+    s.strip_prefix(prefix)
+        .ok_or_else(|| anyhow!("expected {s:?} to start with {prefix:?}"))
+}
+
+pub fn strip_suffix<'a>(s: &'a str, suffix: &str) -> Result<&'a str> {
+    s.strip_suffix(suffix)
+        .ok_or_else(|| anyhow!("expected {s:?} to end with {suffix:?}"))
+}
+
+pub fn number<T>(s: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    s.trim()
+        .parse()
+        .with_context(|| format!("invalid number {s:?}"))
+}
@@ -0,0 +1,299 @@
+//! Small coordinate vectors shared by the movement and geometry puzzles.
+//!
+//! Both types are plain `Copy` structs with componentwise arithmetic. For the
+//! 2D constants, `y` grows upwards, so [`Point2::UP`] is `(0, 1)`.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use num::Signed;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Signed + Copy + Ord> Point2<T> {
+    /// Sum of the absolute componentwise differences.
+    pub fn manhattan(self, other: Point2<T>) -> T {
+        let d = self - other;
+        d.x.abs() + d.y.abs()
+    }
+
+    /// Largest absolute componentwise difference, i.e. king-move distance.
+    pub fn chebyshev(self, other: Point2<T>) -> T {
+        let d = self - other;
+        d.x.abs().max(d.y.abs())
+    }
+
+    /// The componentwise sign: a single, possibly diagonal, step in the direction of `self`.
+    pub fn signum(self) -> Point2<T> {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+}
+
+impl<T: Signed + Copy + Ord> Point3<T> {
+    pub fn manhattan(self, other: Point3<T>) -> T {
+        let d = self - other;
+        d.x.abs() + d.y.abs() + d.z.abs()
+    }
+
+    pub fn chebyshev(self, other: Point3<T>) -> T {
+        let d = self - other;
+        d.x.abs().max(d.y.abs()).max(d.z.abs())
+    }
+
+    pub fn signum(self) -> Point3<T> {
+        Point3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+}
+
+/// Integer component types that the unit-direction constants can be built from.
+pub trait Coord: Copy {
+    const ZERO: Self;
+    const ONE: Self;
+    const NEG_ONE: Self;
+}
+
+macro_rules! coord {
+    ($($t:ty),*) => {$(
+        impl Coord for $t {
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+            const NEG_ONE: $t = -1;
+        }
+    )*};
+}
+
+coord!(i32, i64);
+
+impl<T: Coord> Point2<T> {
+    pub const ORIGIN: Point2<T> = Point2::new(T::ZERO, T::ZERO);
+    pub const UP: Point2<T> = Point2::new(T::ZERO, T::ONE);
+    pub const DOWN: Point2<T> = Point2::new(T::ZERO, T::NEG_ONE);
+    pub const LEFT: Point2<T> = Point2::new(T::NEG_ONE, T::ZERO);
+    pub const RIGHT: Point2<T> = Point2::new(T::ONE, T::ZERO);
+    /// The four orthogonal unit steps, clockwise from up.
+    pub const DIRECTIONS: [Point2<T>; 4] = [Self::UP, Self::RIGHT, Self::DOWN, Self::LEFT];
+}
+
+impl<T: Coord> Point3<T> {
+    pub const ORIGIN: Point3<T> = Point3::new(T::ZERO, T::ZERO, T::ZERO);
+    pub const UNIT_X: Point3<T> = Point3::new(T::ONE, T::ZERO, T::ZERO);
+    pub const UNIT_Y: Point3<T> = Point3::new(T::ZERO, T::ONE, T::ZERO);
+    pub const UNIT_Z: Point3<T> = Point3::new(T::ZERO, T::ZERO, T::ONE);
+    /// The six unit steps to face-adjacent cubes.
+    pub const DIRECTIONS: [Point3<T>; 6] = [
+        Self::UNIT_X,
+        Point3::new(T::NEG_ONE, T::ZERO, T::ZERO),
+        Self::UNIT_Y,
+        Point3::new(T::ZERO, T::NEG_ONE, T::ZERO),
+        Self::UNIT_Z,
+        Point3::new(T::ZERO, T::ZERO, T::NEG_ONE),
+    ];
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Point2<T>;
+    fn add(self, rhs: Point2<T>) -> Point2<T> {
+        Point2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Point2<T>;
+    fn sub(self, rhs: Point2<T>) -> Point2<T> {
+        Point2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point2<T> {
+    type Output = Point2<T>;
+    fn mul(self, rhs: T) -> Point2<T> {
+        Point2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Point2<T>;
+    fn neg(self) -> Point2<T> {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Add<Output = T> + Copy> AddAssign for Point2<T> {
+    fn add_assign(&mut self, rhs: Point2<T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, rhs: Point2<T>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Point3<T>;
+    fn add(self, rhs: Point3<T>) -> Point3<T> {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Point3<T>;
+    fn sub(self, rhs: Point3<T>) -> Point3<T> {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point3<T> {
+    type Output = Point3<T>;
+    fn mul(self, rhs: T) -> Point3<T> {
+        Point3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Point3<T>;
+    fn neg(self) -> Point3<T> {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Add<Output = T> + Copy> AddAssign for Point3<T> {
+    fn add_assign(&mut self, rhs: Point3<T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, rhs: Point3<T>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(Point2 { x, y }: Point2<T>) -> Self {
+        (x, y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(Point3 { x, y, z }: Point3<T>) -> Self {
+        (x, y, z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let mut p = Point2::new(3, -4);
+        assert_eq!(p + Point2::new(1, 1), Point2::new(4, -3));
+        assert_eq!(p - Point2::new(1, 1), Point2::new(2, -5));
+        assert_eq!(p * 2, Point2::new(6, -8));
+        assert_eq!(-p, Point2::new(-3, 4));
+        p += Point2::RIGHT;
+        assert_eq!(p, Point2::new(4, -4));
+        p -= Point2::UP;
+        assert_eq!(p, Point2::new(4, -5));
+
+        let mut q = Point3::new(1, 2, 3);
+        assert_eq!(q + Point3::new(1, 0, -1), Point3::new(2, 2, 2));
+        assert_eq!(q - Point3::new(1, 0, -1), Point3::new(0, 2, 4));
+        assert_eq!(q * -1, -q);
+        q += Point3::UNIT_Z;
+        q -= Point3::UNIT_X;
+        assert_eq!(q, Point3::new(0, 2, 4));
+    }
+
+    #[test]
+    fn distances_and_signum() {
+        let a = Point2::new(1i64, 5);
+        let b = Point2::new(-2, 1);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!((b - a).signum(), Point2::new(-1, -1));
+        assert_eq!(Point2::new(0, 7).signum(), Point2::UP);
+
+        let c = Point3::new(1, -2, 3);
+        let d = Point3::new(4, 2, 3);
+        assert_eq!(c.manhattan(d), 7);
+        assert_eq!(c.chebyshev(d), 4);
+        assert_eq!((d - c).signum(), Point3::new(1, 1, 0));
+        assert_eq!(Point3::new(0, 0, -9).signum(), -Point3::UNIT_Z);
+    }
+
+    #[test]
+    fn directions() {
+        // y grows upwards, and the directions go clockwise from up.
+        assert_eq!(Point2::<i32>::UP, Point2::new(0, 1));
+        assert_eq!(Point2::<i32>::DOWN, -Point2::UP);
+        assert_eq!(Point2::<i32>::LEFT, -Point2::RIGHT);
+        assert_eq!(
+            Point2::<i32>::DIRECTIONS.map(<(i32, i32)>::from),
+            [(0, 1), (1, 0), (0, -1), (-1, 0)]
+        );
+        assert_eq!(
+            Point2::<i64>::DIRECTIONS
+                .into_iter()
+                .fold(Point2::ORIGIN, Add::add),
+            Point2::ORIGIN
+        );
+
+        let dirs = Point3::<i64>::DIRECTIONS;
+        assert!(dirs.iter().all(|d| d.manhattan(Point3::ORIGIN) == 1));
+        for pair in dirs.chunks(2) {
+            assert_eq!(pair[0], -pair[1]);
+        }
+        assert_eq!(
+            [Point3::UNIT_X, Point3::UNIT_Y, Point3::UNIT_Z].map(<(i32, i32, i32)>::from),
+            [(1, 0, 0), (0, 1, 0), (0, 0, 1)]
+        );
+    }
+
+    #[test]
+    fn tuple_conversions() {
+        let p: Point2<i32> = (2, -3).into();
+        assert_eq!(p, Point2::new(2, -3));
+        assert_eq!(<(i32, i32)>::from(p), (2, -3));
+        let q = Point3::from((1u8, 2, 3));
+        assert_eq!((q.x, q.y, q.z), (1, 2, 3));
+        let (x, y, z) = q.into();
+        assert_eq!((x, y, z), (1, 2, 3));
+    }
+}
@@ -0,0 +1,72 @@
+//! Every year's generators and solvers in one table, so they can be driven
+//! without going through `cargo aoc`.
+
+use anyhow::Result;
+
+use crate::{y2021, y2022};
+
+/// One part of a day, bound to the generator output it runs on.
+pub type Solver = Box<dyn Fn() -> Result<String>>;
+
+/// What a part can return: a printable answer, or a `Result` of one for parts
+/// that can find their input doesn't suit them.
+pub trait Answer {
+    fn answer(self) -> Result<String>;
+}
+
+macro_rules! display_answer {
+    ($($ty:ty),+) => {
+        $(impl Answer for $ty {
+            fn answer(self) -> Result<String> {
+                Ok(self.to_string())
+            }
+        })+
+    };
+}
+display_answer!(i32, i64, u32, u64, usize, String);
+
+impl<T: Answer> Answer for Result<T> {
+    fn answer(self) -> Result<String> {
+        self?.answer()
+    }
+}
+
+pub struct Day {
+    pub day: u32,
+    /// Runs the generator and returns a solver for each part, sharing its output.
+    pub parse: fn(&str) -> Result<Vec<Solver>>,
+}
+
+/// Builds a [`Day`] from a module's `parse` function and its parts.
+macro_rules! day {
+    ($day:literal, $module:ident, $($part:ident),+) => {
+        $crate::solutions::Day {
+            day: $day,
+            parse: |input| {
+                let parsed = ::std::rc::Rc::new($module::parse(input)?);
+                Ok(vec![$({
+                    let parsed = ::std::rc::Rc::clone(&parsed);
+                    Box::new(move || $crate::solutions::Answer::answer($module::$part(&parsed))) as $crate::solutions::Solver
+                }),+])
+            },
+        }
+    };
+}
+pub(crate) use day;
+
+/// The years that have solutions, oldest first.
+pub const YEARS: &[u32] = &[2021, 2022];
+
+/// The solved days of `year`, if there are any.
+pub fn days(year: u32) -> Option<&'static [Day]> {
+    match year {
+        2021 => Some(y2021::DAYS),
+        2022 => Some(y2022::DAYS),
+        _ => None,
+    }
+}
+
+/// Looks up a single day of `year`.
+pub fn find_day(year: u32, day: u32) -> Option<&'static Day> {
+    days(year)?.iter().find(|entry| entry.day == day)
+}
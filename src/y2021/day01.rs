@@ -0,0 +1,52 @@
+use anyhow::Result;
+
+use crate::parsing::{self, number};
+
+pub fn parse(input: &str) -> Result<Vec<u32>> {
+    parsing::parse_lines(1, input, number)
+}
+
+/// How many depths are deeper than the one `gap` readings before them.
+fn count_increases(depths: &[u32], gap: usize) -> usize {
+    depths
+        .iter()
+        .zip(&depths[gap.min(depths.len())..])
+        .filter(|(before, after)| after > before)
+        .count()
+}
+
+pub fn part1(input: &[u32]) -> usize {
+    count_increases(input, 1)
+}
+
+/// Comparing sums of three-reading windows that overlap in two readings only
+/// comes down to the readings at either end.
+pub fn part2(input: &[u32]) -> usize {
+    count_increases(input, 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "199
+200
+208
+210
+200
+207
+240
+269
+260
+263";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 7);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 5);
+    }
+}
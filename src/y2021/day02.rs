@@ -0,0 +1,72 @@
+use anyhow::{bail, Result};
+
+use crate::parsing::{self, number, split_once};
+
+#[derive(Clone, Copy, Debug)]
+pub enum Command {
+    Forward(i64),
+    Down(i64),
+    Up(i64),
+}
+
+pub fn parse(input: &str) -> Result<Vec<Command>> {
+    parsing::parse_lines(2, input, |line| {
+        let (direction, amount) = split_once(line, " ")?;
+        let amount = number(amount)?;
+        Ok(match direction {
+            "forward" => Command::Forward(amount),
+            "down" => Command::Down(amount),
+            "up" => Command::Up(amount),
+            _ => bail!("unknown direction {direction:?}"),
+        })
+    })
+}
+
+pub fn part1(input: &[Command]) -> i64 {
+    let (mut position, mut depth) = (0, 0);
+    for &command in input {
+        match command {
+            Command::Forward(n) => position += n,
+            Command::Down(n) => depth += n,
+            Command::Up(n) => depth -= n,
+        }
+    }
+    position * depth
+}
+
+pub fn part2(input: &[Command]) -> i64 {
+    let (mut position, mut depth, mut aim) = (0, 0, 0);
+    for &command in input {
+        match command {
+            Command::Forward(n) => {
+                position += n;
+                depth += aim * n;
+            }
+            Command::Down(n) => aim += n,
+            Command::Up(n) => aim -= n,
+        }
+    }
+    position * depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 150);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 900);
+    }
+}
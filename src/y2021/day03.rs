@@ -0,0 +1,130 @@
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::parsing;
+
+/// The diagnostic report: equally wide binary numbers.
+pub struct Report {
+    pub width: usize,
+    pub numbers: Vec<u32>,
+}
+
+pub fn parse(input: &str) -> Result<Report> {
+    let mut width = None;
+    let numbers = parsing::parse_lines(3, input, |line| {
+        if line.is_empty() || line.len() > 32 {
+            bail!("expected 1 to 32 binary digits");
+        }
+        match width {
+            None => width = Some(line.len()),
+            Some(width) if width != line.len() => {
+                bail!(
+                    "expected {width} digits like the first line, got {}",
+                    line.len()
+                )
+            }
+            Some(_) => {}
+        }
+        u32::from_str_radix(line, 2).context("invalid binary number")
+    })?;
+    let width = width.ok_or_else(|| anyhow!("day 3: empty report"))?;
+    Ok(Report { width, numbers })
+}
+
+fn ones_at(numbers: &[u32], bit: usize) -> usize {
+    numbers.iter().filter(|&&n| n >> bit & 1 == 1).count()
+}
+
+/// The bit value at `bit` shared by at least half of `numbers`; ties go to 1.
+fn most_common(numbers: &[u32], bit: usize) -> u32 {
+    u32::from(2 * ones_at(numbers, bit) >= numbers.len())
+}
+
+/// Reports can be 32 digits wide, so the mask and product are computed in u64.
+pub fn part1(input: &Report) -> u64 {
+    let gamma = (0..input.width).fold(0, |gamma, bit| {
+        gamma | u64::from(most_common(&input.numbers, bit)) << bit
+    });
+    let epsilon = !gamma & ((1 << input.width) - 1);
+    gamma * epsilon
+}
+
+/// Narrows `numbers` down bit by bit from the top, keeping those whose bit
+/// matches the most common value (or the least common, if `keep_least`). A
+/// bit that every remaining number shares is skipped rather than filtering
+/// them all out.
+fn rating(input: &Report, keep_least: bool) -> u32 {
+    let mut numbers = input.numbers.clone();
+    for bit in (0..input.width).rev() {
+        if numbers.len() == 1 {
+            break;
+        }
+        let ones = ones_at(&numbers, bit);
+        if ones == 0 || ones == numbers.len() {
+            continue;
+        }
+        let wanted = most_common(&numbers, bit) ^ u32::from(keep_least);
+        numbers.retain(|&n| n >> bit & 1 == wanted);
+    }
+    numbers[0]
+}
+
+/// The report is never empty, so both ratings always find a number.
+pub fn part2(input: &Report) -> u64 {
+    u64::from(rating(input, false)) * u64::from(rating(input, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 198);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 230);
+    }
+
+    #[test]
+    fn full_width_reports() {
+        let ones = "1".repeat(32);
+        let zeros = "0".repeat(32);
+        let report = parse(&format!("{ones}\n{ones}\n{zeros}")).unwrap();
+        assert_eq!(part1(&report), 0);
+        assert_eq!(part2(&report), 0);
+
+        let report = parse(&format!(
+            "0{}\n1{}\n1{}",
+            &ones[1..],
+            &zeros[1..],
+            &zeros[1..]
+        ))
+        .unwrap();
+        // Gamma is 1000...0 and epsilon is 0111...1.
+        assert_eq!(part1(&report), (1 << 31) * ((1 << 31) - 1));
+        assert_eq!(part2(&report), (1 << 31) * ((1 << 31) - 1));
+        assert!(parse(&format!("{ones}1")).is_err());
+    }
+
+    #[test]
+    fn shared_bits_are_skipped() {
+        assert_eq!(part2(&parse("10\n11").unwrap()), 3 * 2);
+        assert_eq!(part2(&parse("110\n110\n111").unwrap()), 6 * 7);
+        assert_eq!(part2(&parse("101\n101").unwrap()), 5 * 5);
+    }
+}
@@ -0,0 +1,15 @@
+//! Advent of Code 2021. `cargo aoc` only knows about one year per crate, so
+//! these days skip the `#[aoc]` attributes and are only reachable through
+//! [`DAYS`].
+
+use crate::solutions::{day, Day};
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+
+pub const DAYS: &[Day] = &[
+    day!(1, day01, part1, part2),
+    day!(2, day02, part1, part2),
+    day!(3, day03, part1, part2),
+];
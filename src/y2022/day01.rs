@@ -0,0 +1,400 @@
+use std::{cmp::Reverse, collections::BinaryHeap, io::BufRead};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::parsing::{self, number, Line};
+
+/// Each blank line ends an elf's inventory, so every extra blank line in a run
+/// is an elf carrying nothing. Blank lines at the end of the input are ignored.
+#[aoc_generator(day1)]
+pub fn parse(input: &str) -> Result<Vec<Vec<i32>>> {
+    let mut lines = parsing::lines(1, input).peekable();
+    let mut elves = Vec::new();
+    while lines.peek().is_some() {
+        let elf = lines
+            .by_ref()
+            .take_while(|line| !line.text.trim().is_empty())
+            .map(|line| line.parse(number))
+            .collect::<Result<_>>()?;
+        elves.push(elf);
+    }
+    Ok(elves)
+}
+
+/// An elf's calorie total, with its position among the elves (from 0).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Carrier {
+    pub index: usize,
+    pub total: i64,
+}
+
+/// The `k` largest carriers pushed so far, kept in a min-heap so that each
+/// push costs `O(log k)` and memory stays at `O(k)`. Ties go to the earlier elf.
+pub struct TopK {
+    k: usize,
+    heap: BinaryHeap<Reverse<(i64, Reverse<usize>)>>,
+}
+
+impl TopK {
+    pub fn new(k: usize) -> TopK {
+        TopK {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    pub fn push(&mut self, Carrier { index, total }: Carrier) {
+        self.heap.push(Reverse((total, Reverse(index))));
+        if self.heap.len() > self.k {
+            self.heap.pop();
+        }
+    }
+
+    /// The kept carriers, largest total first.
+    pub fn into_sorted_vec(self) -> Vec<Carrier> {
+        // The heap holds `Reverse`d entries, so ascending order is largest first.
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((total, Reverse(index)))| Carrier { index, total })
+            .collect()
+    }
+}
+
+impl Extend<Carrier> for TopK {
+    fn extend<I: IntoIterator<Item = Carrier>>(&mut self, iter: I) {
+        for carrier in iter {
+            self.push(carrier);
+        }
+    }
+}
+
+/// Reads elf inventories in the puzzle format from `reader` and returns the
+/// `k` elves carrying the most calories, largest first. Only one line and the
+/// `k` best totals are held in memory at a time. Elves are counted the same
+/// way as in [`parse`], empty ones included.
+pub fn top_k(mut reader: impl BufRead, k: usize) -> Result<Vec<Carrier>> {
+    let mut top = TopK::new(k);
+    let mut buf = String::new();
+    let mut current = Carrier { index: 0, total: 0 };
+    // Blank lines since the last item; they only end elves once another item follows.
+    let mut blanks = 0;
+    let mut any_items = false;
+    for number in 1.. {
+        buf.clear();
+        if reader.read_line(&mut buf)? == 0 {
+            break;
+        }
+        let text = buf.trim();
+        if text.is_empty() {
+            blanks += 1;
+            continue;
+        }
+        let calories: i64 = Line {
+            day: 1,
+            number,
+            text,
+        }
+        .parse(parsing::number)?;
+        for _ in 0..blanks {
+            top.push(current);
+            current = Carrier {
+                index: current.index + 1,
+                total: 0,
+            };
+        }
+        blanks = 0;
+        current.total += calories;
+        any_items = true;
+    }
+    if any_items {
+        top.push(current);
+    }
+    Ok(top.into_sorted_vec())
+}
+
+fn carriers(inp: &[Vec<i32>]) -> impl Iterator<Item = Carrier> + '_ {
+    inp.iter().enumerate().map(|(index, elf)| Carrier {
+        index,
+        total: elf.iter().map(|&calories| i64::from(calories)).sum(),
+    })
+}
+
+/// Summary statistics over the elves' calorie totals.
+pub struct Stats {
+    /// Every elf's total, in ascending order.
+    totals: Vec<i64>,
+    empty_elves: usize,
+    largest_item: Option<i32>,
+}
+
+/// A histogram bucket: how many elves carry `start..start + width` calories.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+pub struct Bucket {
+    pub start: i64,
+    pub count: usize,
+}
+
+/// A [`Stats`] snapshot in a form that can be written out as JSON. Fields
+/// that need at least one elf are `None` for an empty inventory.
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct Report {
+    pub elves: usize,
+    /// Elves carrying nothing, written as extra blank lines between inventories.
+    pub empty_elves: usize,
+    pub total: i64,
+    pub mean: Option<f64>,
+    pub median: Option<f64>,
+    /// `(percentile, total)` pairs.
+    pub percentiles: Vec<(f64, i64)>,
+    pub bucket_width: i64,
+    pub histogram: Vec<Bucket>,
+    pub largest_item: Option<i32>,
+}
+
+impl Report {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl Stats {
+    pub fn new(inp: &[Vec<i32>]) -> Stats {
+        let mut totals: Vec<i64> = carriers(inp).map(|carrier| carrier.total).collect();
+        totals.sort_unstable();
+        Stats {
+            totals,
+            empty_elves: inp.iter().filter(|elf| elf.is_empty()).count(),
+            largest_item: inp.iter().flatten().copied().max(),
+        }
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.totals.is_empty() {
+            return None;
+        }
+        Some(self.totals.iter().sum::<i64>() as f64 / self.totals.len() as f64)
+    }
+
+    /// The middle total, or the mean of the two middle ones for an even count.
+    pub fn median(&self) -> Option<f64> {
+        let n = self.totals.len();
+        match n {
+            0 => None,
+            _ if n % 2 == 1 => Some(self.totals[n / 2] as f64),
+            _ => Some((self.totals[n / 2 - 1] + self.totals[n / 2]) as f64 / 2.0),
+        }
+    }
+
+    /// The nearest-rank `p`th percentile (`0.0..=100.0`): the smallest total
+    /// that at least `p` percent of the elves carry no more than.
+    pub fn percentile(&self, p: f64) -> Option<i64> {
+        assert!((0.0..=100.0).contains(&p), "percentile {p} out of range");
+        let n = self.totals.len();
+        let rank = (p / 100.0 * n as f64).ceil() as usize;
+        self.totals.get(rank.clamp(1, n.max(1)) - 1).copied()
+    }
+
+    /// Counts of totals in `width`-calorie buckets, lowest first. Only
+    /// buckets holding at least one elf are listed, so widely spread totals
+    /// don't need a bucket for every gap between them.
+    pub fn histogram(&self, width: i64) -> Vec<Bucket> {
+        assert!(width > 0, "bucket width must be positive");
+        self.totals
+            .chunk_by(|a, b| a.div_euclid(width) == b.div_euclid(width))
+            .map(|bucket| Bucket {
+                start: bucket[0].div_euclid(width) * width,
+                count: bucket.len(),
+            })
+            .collect()
+    }
+
+    pub fn report(&self, percentiles: &[f64], bucket_width: i64) -> Report {
+        Report {
+            elves: self.totals.len(),
+            empty_elves: self.empty_elves,
+            total: self.totals.iter().sum(),
+            mean: self.mean(),
+            median: self.median(),
+            percentiles: percentiles
+                .iter()
+                .filter_map(|&p| Some((p, self.percentile(p)?)))
+                .collect(),
+            bucket_width,
+            histogram: self.histogram(bucket_width),
+            largest_item: self.largest_item,
+        }
+    }
+}
+
+#[aoc(day1, part1)]
+pub fn pt1(inp: &[Vec<i32>]) -> i64 {
+    carriers(inp).map(|carrier| carrier.total).max().unwrap()
+}
+
+#[aoc(day1, part2)]
+pub fn pt2(inp: &[Vec<i32>]) -> i64 {
+    let mut top = TopK::new(3);
+    top.extend(carriers(inp));
+    top.into_sorted_vec()
+        .iter()
+        .map(|carrier| carrier.total)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(pt1(&parse(EXAMPLE).unwrap()), 24000);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(pt2(&parse(EXAMPLE).unwrap()), 45000);
+    }
+
+    fn carrier(index: usize, total: i64) -> Carrier {
+        Carrier { index, total }
+    }
+
+    #[test]
+    fn top_k_streams_the_example() {
+        assert_eq!(
+            top_k(EXAMPLE.as_bytes(), 3).unwrap(),
+            [carrier(3, 24000), carrier(2, 11000), carrier(4, 10000)]
+        );
+        assert_eq!(top_k(EXAMPLE.as_bytes(), 0).unwrap(), []);
+        assert_eq!(top_k(EXAMPLE.as_bytes(), 10).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn top_k_breaks_ties_by_position_and_counts_empty_elves() {
+        let input = "\n\n5\n\n\n\n2\n3\n\n1\n4\n\n\n";
+        assert_eq!(
+            top_k(input.as_bytes(), 2).unwrap(),
+            [carrier(2, 5), carrier(5, 5)]
+        );
+        assert_eq!(
+            top_k(input.as_bytes(), 10).unwrap(),
+            [
+                carrier(2, 5),
+                carrier(5, 5),
+                carrier(6, 5),
+                carrier(0, 0),
+                carrier(1, 0),
+                carrier(3, 0),
+                carrier(4, 0),
+            ]
+        );
+        let elves = parse(input).unwrap();
+        assert_eq!(elves.len(), 7);
+        assert_eq!(Stats::new(&elves).report(&[], 1).empty_elves, 4);
+        assert_eq!(top_k("\n\n".as_bytes(), 1).unwrap(), []);
+    }
+
+    #[test]
+    fn top_k_reports_the_bad_line() {
+        let err = top_k("1\n2\n\nthree\n".as_bytes(), 1).unwrap_err();
+        assert!(err.to_string().starts_with("day 1, line 4"), "{err}");
+    }
+
+    #[test]
+    fn stats_example() {
+        // The extra blank line after the second elf is an elf with nothing.
+        let elves = parse(&EXAMPLE.replace("4000\n", "4000\n\n")).unwrap();
+        assert!(elves[2].is_empty());
+        let stats = Stats::new(&elves);
+        // Totals: 0, 4000, 6000, 10000, 11000, 24000.
+        assert_eq!(stats.mean(), Some(55000.0 / 6.0));
+        assert_eq!(stats.median(), Some(8000.0));
+        assert_eq!(stats.percentile(0.0), Some(0));
+        assert_eq!(stats.percentile(50.0), Some(6000));
+        assert_eq!(stats.percentile(90.0), Some(24000));
+        assert_eq!(
+            stats.histogram(10000),
+            [
+                Bucket { start: 0, count: 3 },
+                Bucket {
+                    start: 10000,
+                    count: 2
+                },
+                Bucket {
+                    start: 20000,
+                    count: 1
+                },
+            ]
+        );
+
+        let report = stats.report(&[50.0], 10000);
+        assert_eq!(report.elves, 6);
+        assert_eq!(report.empty_elves, 1);
+        assert_eq!(report.total, 55000);
+        assert_eq!(report.largest_item, Some(10000));
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["percentiles"], serde_json::json!([[50.0, 6000]]));
+        assert_eq!(json["histogram"][2]["count"], 1);
+    }
+
+    #[test]
+    fn histograms_skip_empty_buckets() {
+        let stats = Stats::new(&[vec![0], vec![i32::MAX], vec![1, 1], vec![-5]]);
+        assert_eq!(
+            stats.histogram(1),
+            [
+                Bucket {
+                    start: -5,
+                    count: 1
+                },
+                Bucket { start: 0, count: 1 },
+                Bucket { start: 2, count: 1 },
+                Bucket {
+                    start: i64::from(i32::MAX),
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!(
+            stats.histogram(10),
+            [
+                Bucket {
+                    start: -10,
+                    count: 1
+                },
+                Bucket { start: 0, count: 2 },
+                Bucket {
+                    start: i64::from(i32::MAX) / 10 * 10,
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn stats_of_nothing() {
+        let report = Stats::new(&[]).report(&[50.0], 1000);
+        assert_eq!(report.mean, None);
+        assert_eq!(report.median, None);
+        assert!(report.percentiles.is_empty());
+        assert!(report.histogram.is_empty());
+        assert_eq!(report.largest_item, None);
+    }
+}
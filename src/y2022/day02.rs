@@ -0,0 +1,312 @@
+use anyhow::{bail, Result};
+use std::iter::Iterator as _;
+
+use crate::parsing::{self, split_once};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RPS {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+/// Outcomes in the order [`Rules`] scores them.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    Lose,
+    Tie,
+    Win,
+}
+
+use Outcome::*;
+use RPS::*;
+
+impl RPS {
+    fn from_opponent(s: &str) -> Result<Self> {
+        match s {
+            "A" => Ok(Rock),
+            "B" => Ok(Paper),
+            "C" => Ok(Scissors),
+            _ => bail!("unknown opponent move {s:?}"),
+        }
+    }
+}
+
+/// The second column of the strategy guide, whose meaning depends on the
+/// [`Interpretation`] reading it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Code {
+    X,
+    Y,
+    Z,
+}
+
+impl Code {
+    fn parse(s: &str) -> Result<Self> {
+        match s {
+            "X" => Ok(Code::X),
+            "Y" => Ok(Code::Y),
+            "Z" => Ok(Code::Z),
+            _ => bail!("unknown strategy code {s:?}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Round {
+    pub opponent: RPS,
+    pub code: Code,
+}
+
+/// A way of reading a round's [`Code`] as the move to play.
+pub trait Interpretation {
+    fn response(rules: &Rules, round: Round) -> usize;
+}
+
+/// Part 1's reading: X, Y and Z are Rock, Paper and Scissors.
+pub struct AsMove;
+
+impl Interpretation for AsMove {
+    fn response(_: &Rules, round: Round) -> usize {
+        round.code as usize
+    }
+}
+
+/// Part 2's reading: X, Y and Z say to lose, draw or win.
+pub struct AsOutcome;
+
+impl Interpretation for AsOutcome {
+    fn response(rules: &Rules, round: Round) -> usize {
+        let outcome = match round.code {
+            Code::X => Lose,
+            Code::Y => Tie,
+            Code::Z => Win,
+        };
+        rules.pick_to_get_outcome(round.opponent as usize, outcome)
+    }
+}
+
+/// The guide's total score when its codes are read by `I`.
+pub fn guide_score<I: Interpretation>(rules: &Rules, inp: &[Round]) -> i32 {
+    inp.iter()
+        .map(|&round| rules.score(I::response(rules, round), round.opponent as usize))
+        .sum()
+}
+
+/// A cyclic game with an odd number of moves, at least 3, numbered from 0. Each move
+/// beats the `(n - 1) / 2` moves just before it in the cycle (wrapping
+/// around) and loses to the `(n - 1) / 2` just after it.
+///
+/// Rock-paper-scissors is moves `[Rock, Paper, Scissors]`; Rock-Paper-
+/// Scissors-Lizard-Spock is `[Rock, Spock, Paper, Lizard, Scissors]`.
+#[derive(Clone, Debug)]
+pub struct Rules {
+    /// The score for playing each move.
+    move_scores: Vec<i32>,
+    /// The score for each [`Outcome`], indexed by its discriminant.
+    outcome_scores: [i32; 3],
+}
+
+impl Rules {
+    pub fn new(move_scores: Vec<i32>, outcome_scores: [i32; 3]) -> Result<Rules> {
+        // With a single move nothing beats anything, so there's no way to win.
+        if move_scores.len() < 3 || move_scores.len().is_multiple_of(2) {
+            bail!(
+                "a cyclic game needs an odd number of moves, at least 3, got {}",
+                move_scores.len()
+            );
+        }
+        Ok(Rules {
+            move_scores,
+            outcome_scores,
+        })
+    }
+
+    /// The puzzle's scoring: 1, 2 or 3 for the move, plus 0, 3 or 6 for the outcome.
+    pub fn rock_paper_scissors() -> Rules {
+        Rules::new(vec![1, 2, 3], [0, 3, 6]).unwrap()
+    }
+
+    pub fn moves(&self) -> usize {
+        self.move_scores.len()
+    }
+
+    pub fn outcome(&self, me: usize, opp: usize) -> Outcome {
+        let n = self.moves();
+        match (me + n - opp) % n {
+            0 => Tie,
+            ahead if ahead <= n / 2 => Win,
+            _ => Lose,
+        }
+    }
+
+    pub fn score(&self, me: usize, opp: usize) -> i32 {
+        self.move_scores[me] + self.outcome_scores[self.outcome(me, opp) as usize]
+    }
+
+    /// The first move that gets `outcome` against `opp`.
+    pub fn pick_to_get_outcome(&self, opp: usize, outcome: Outcome) -> usize {
+        (0..self.moves())
+            .find(|&me| self.outcome(me, opp) == outcome)
+            .unwrap()
+    }
+}
+
+#[aoc_generator(day2)]
+pub fn parse(input: &str) -> Result<Vec<Round>> {
+    parsing::parse_lines(2, input, |s| {
+        let (opponent, code) = split_once(s, " ")?;
+        Ok(Round {
+            opponent: RPS::from_opponent(opponent)?,
+            code: Code::parse(code)?,
+        })
+    })
+}
+
+/// How often the opponent plays each move in the guide, indexed by [`RPS`].
+pub fn opponent_frequencies(inp: &[Round]) -> [usize; 3] {
+    let mut counts = [0; 3];
+    for round in inp {
+        counts[round.opponent as usize] += 1;
+    }
+    counts
+}
+
+/// The highest total possible against the guide's opponent moves if we could
+/// answer each round however we liked.
+pub fn best_possible_score(rules: &Rules, inp: &[Round]) -> i32 {
+    inp.iter()
+        .map(|round| {
+            (0..rules.moves())
+                .map(|me| rules.score(me, round.opponent as usize))
+                .max()
+                .unwrap()
+        })
+        .sum()
+}
+
+/// The expected score for playing `me` against an opponent who plays each
+/// move with the probability given in `mix`.
+pub fn expected_score(rules: &Rules, me: usize, mix: &[f64]) -> Result<f64> {
+    if mix.len() != rules.moves() {
+        bail!(
+            "expected {} probabilities, one per move, got {}",
+            rules.moves(),
+            mix.len()
+        );
+    }
+    if mix.iter().any(|p| !(0.0..=1.0).contains(p)) || (mix.iter().sum::<f64>() - 1.0).abs() > 1e-9
+    {
+        bail!("probabilities {mix:?} don't form a distribution");
+    }
+    Ok(mix
+        .iter()
+        .enumerate()
+        .map(|(opp, p)| p * f64::from(rules.score(me, opp)))
+        .sum())
+}
+
+/// The expected total for the guide's moves, read as [`AsMove`], when the
+/// opponent ignores the guide and plays `mix` every round.
+pub fn expected_guide_score(rules: &Rules, inp: &[Round], mix: &[f64]) -> Result<f64> {
+    inp.iter()
+        .map(|&round| expected_score(rules, AsMove::response(rules, round), mix))
+        .sum()
+}
+
+/// The move with the highest expected score against `mix`, and that score.
+/// Ties go to the lowest-numbered move.
+pub fn best_response(rules: &Rules, mix: &[f64]) -> Result<(usize, f64)> {
+    let mut best: Option<(usize, f64)> = None;
+    for me in 0..rules.moves() {
+        let score = expected_score(rules, me, mix)?;
+        if best.is_none_or(|(_, best)| score > best) {
+            best = Some((me, score));
+        }
+    }
+    Ok(best.unwrap())
+}
+
+#[aoc(day2, part1)]
+pub fn pt1(inp: &[Round]) -> i32 {
+    guide_score::<AsMove>(&Rules::rock_paper_scissors(), inp)
+}
+
+#[aoc(day2, part2)]
+pub fn pt2(inp: &[Round]) -> i32 {
+    guide_score::<AsOutcome>(&Rules::rock_paper_scissors(), inp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "A Y
+B X
+C Z";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(pt1(&parse(EXAMPLE).unwrap()), 15);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(pt2(&parse(EXAMPLE).unwrap()), 12);
+    }
+
+    #[test]
+    fn rock_paper_scissors_lizard_spock() {
+        let [rock, spock, paper, lizard, scissors] = [0, 1, 2, 3, 4];
+        let rules = Rules::new(vec![1, 2, 3, 4, 5], [0, 3, 6]).unwrap();
+        for (winner, loser) in [
+            (scissors, paper),
+            (paper, rock),
+            (rock, lizard),
+            (lizard, spock),
+            (spock, scissors),
+            (scissors, lizard),
+            (lizard, paper),
+            (paper, spock),
+            (spock, rock),
+            (rock, scissors),
+        ] {
+            assert_eq!(rules.outcome(winner, loser), Win);
+            assert_eq!(rules.outcome(loser, winner), Lose);
+        }
+        assert_eq!(rules.outcome(lizard, lizard), Tie);
+        assert_eq!(rules.score(spock, rock), 8);
+        assert_eq!(rules.pick_to_get_outcome(spock, Win), paper);
+        assert!(Rules::new(vec![1, 2, 3, 4], [0, 3, 6]).is_err());
+        assert!(Rules::new(vec![1], [0, 3, 6]).is_err());
+        assert!(Rules::new(vec![], [0, 3, 6]).is_err());
+    }
+
+    #[test]
+    fn analysis_example() {
+        let guide = parse(EXAMPLE).unwrap();
+        let rules = Rules::rock_paper_scissors();
+        assert_eq!(opponent_frequencies(&guide), [1, 1, 1]);
+        // Paper beats Rock for 8, Scissors beats Paper for 9, Rock beats Scissors for 7.
+        assert_eq!(best_possible_score(&rules, &guide), 24);
+
+        let uniform = [1.0 / 3.0; 3];
+        // Every move scores 3 on average from the outcome plus its own 1 to 3.
+        assert!((expected_guide_score(&rules, &guide, &uniform).unwrap() - 15.0).abs() < 1e-9);
+        let (me, score) = best_response(&rules, &[0.0, 0.0, 1.0]).unwrap();
+        assert_eq!((me, score), (Rock as usize, 7.0));
+        assert!(expected_score(&rules, 0, &[0.5, 0.5]).is_err());
+        assert!(expected_score(&rules, 0, &[0.5, 0.6, -0.1]).is_err());
+    }
+
+    #[test]
+    fn unknown_letters_name_the_line() {
+        let err = parse("A Y\nB Q\n").unwrap_err();
+        assert!(err.to_string().starts_with("day 2, line 2"), "{err}");
+        assert!(
+            format!("{err:#}").contains("unknown strategy code \"Q\""),
+            "{err:#}"
+        );
+        assert!(parse("D X").is_err());
+    }
+}
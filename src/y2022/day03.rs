@@ -0,0 +1,207 @@
+use std::iter::Iterator as _;
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::parsing;
+
+/// The group size part 2 looks for badges in.
+const GROUP_SIZE: usize = 3;
+
+/// A set of item types, one bit per priority: bit 0 is `a` and bit 51 is `Z`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const EMPTY: ItemSet = ItemSet(0);
+
+    /// `a` to `z` have priorities 1 to 26, and `A` to `Z` have 27 to 52.
+    pub fn priority(item: char) -> Option<u32> {
+        match item {
+            'a'..='z' => Some(item as u32 - 'a' as u32 + 1),
+            'A'..='Z' => Some(item as u32 - 'A' as u32 + 27),
+            _ => None,
+        }
+    }
+
+    fn item(priority: u32) -> char {
+        let base = if priority <= 26 { b'a' } else { b'A' - 26 };
+        char::from(base + priority as u8 - 1)
+    }
+
+    pub fn from_items(items: &str) -> Result<ItemSet> {
+        items.chars().try_fold(ItemSet::EMPTY, |set, item| {
+            let priority =
+                ItemSet::priority(item).ok_or_else(|| anyhow!("unexpected item {item:?}"))?;
+            Ok(set.union(ItemSet(1 << (priority - 1))))
+        })
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The items in priority order.
+    pub fn items(self) -> impl Iterator<Item = char> {
+        (1..=52)
+            .filter(move |priority| self.0 >> (priority - 1) & 1 == 1)
+            .map(ItemSet::item)
+    }
+
+    /// The priority of the one item in the set, or an error if there isn't
+    /// exactly one.
+    pub fn single_priority(self) -> Result<u32> {
+        match self.len() {
+            0 => bail!("no common item"),
+            1 => Ok(self.0.trailing_zeros() + 1),
+            _ => bail!("several common items: {}", self.items().collect::<String>()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rucksack {
+    pub compartments: [ItemSet; 2],
+}
+
+impl Rucksack {
+    pub fn items(&self) -> ItemSet {
+        self.compartments[0].union(self.compartments[1])
+    }
+
+    /// The priority of the item type in both compartments.
+    pub fn misplaced(&self) -> Result<u32> {
+        self.compartments[0]
+            .intersection(self.compartments[1])
+            .single_priority()
+    }
+}
+
+/// The sum of the badge priorities of consecutive groups of `group_size`
+/// rucksacks, where each group must share exactly one item type.
+pub fn badge_priorities(inp: &[Rucksack], group_size: usize) -> Result<u32> {
+    if group_size == 0 {
+        bail!("day 3: groups need at least one rucksack");
+    }
+    if !inp.len().is_multiple_of(group_size) {
+        bail!(
+            "day 3: {} rucksacks don't split into groups of {group_size}",
+            inp.len()
+        );
+    }
+    inp.chunks(group_size)
+        .enumerate()
+        .map(|(i, group)| {
+            let common = group
+                .iter()
+                .map(Rucksack::items)
+                .reduce(ItemSet::intersection)
+                .unwrap();
+            common.single_priority().map_err(|err| {
+                let first = i * group_size + 1;
+                anyhow!("day 3, lines {first}-{}: {err}", first + group_size - 1)
+            })
+        })
+        .sum()
+}
+
+#[aoc_generator(day3)]
+pub fn parse(input: &str) -> Result<Vec<Rucksack>> {
+    parsing::parse_lines(3, input, |line| {
+        if !line.is_ascii() {
+            bail!("rucksack has an item that isn't a letter");
+        }
+        if line.len() % 2 != 0 {
+            bail!("rucksack has an odd number of items");
+        }
+        let (a, b) = line.split_at(line.len() / 2);
+        let rucksack = Rucksack {
+            compartments: [ItemSet::from_items(a)?, ItemSet::from_items(b)?],
+        };
+        rucksack.misplaced()?;
+        Ok(rucksack)
+    })
+}
+
+#[aoc(day3, part1)]
+pub fn pt1(inp: &[Rucksack]) -> u32 {
+    inp.iter()
+        .map(|rucksack| rucksack.misplaced().expect("parse checks each rucksack"))
+        .sum()
+}
+
+#[aoc(day3, part2)]
+pub fn pt2(inp: &[Rucksack]) -> Result<u32> {
+    badge_priorities(inp, GROUP_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(pt1(&parse(EXAMPLE).unwrap()), 157);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(pt2(&parse(EXAMPLE).unwrap()).unwrap(), 70);
+    }
+
+    #[test]
+    fn item_sets() {
+        assert_eq!(ItemSet::priority('p'), Some(16));
+        assert_eq!(ItemSet::priority('L'), Some(38));
+        assert_eq!(ItemSet::priority('1'), None);
+        let set = ItemSet::from_items("zAaZa").unwrap();
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.items().collect::<String>(), "azAZ");
+        assert_eq!(
+            set.intersection(ItemSet::from_items("Zq").unwrap())
+                .single_priority()
+                .unwrap(),
+            52
+        );
+    }
+
+    #[test]
+    fn group_sizes_and_errors() {
+        let rucksacks = parse(EXAMPLE).unwrap();
+        assert!(badge_priorities(&rucksacks, 4).is_err());
+        assert!(badge_priorities(&rucksacks, 0).is_err());
+
+        let err = badge_priorities(&rucksacks, 2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 3, lines 1-2: several common items: frsFM"
+        );
+        let err = parse("abca\nabab").unwrap_err();
+        assert!(err.to_string().starts_with("day 3, line 2"), "{err}");
+        let err = parse("aa\naéa").unwrap_err();
+        assert!(err.to_string().starts_with("day 3, line 2"), "{err}");
+
+        // Part 1 doesn't care how the rucksacks group, so only part 2 rejects these.
+        let four = parse("aa\nbb\ncc\ndd").unwrap();
+        assert_eq!(pt1(&four), 10);
+        assert!(pt2(&four).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use std::iter::Iterator as _;
+use std::ops::RangeInclusive;
+
+use anyhow::Result;
+use petgraph::graph::{NodeIndex, UnGraph};
+
+use crate::{
+    interval::IntervalSet,
+    parsing::{self, number, split_once},
+};
+
+#[aoc_generator(day4)]
+pub fn parse(input: &str) -> Result<Vec<(RangeInclusive<i32>, RangeInclusive<i32>)>> {
+    fn parse_range(s: &str) -> Result<RangeInclusive<i32>> {
+        let (lo, hi) = split_once(s, "-")?;
+        Ok(number(lo)?..=number(hi)?)
+    }
+    parsing::parse_lines(4, input, |line| {
+        let (l, r) = split_once(line, ",")?;
+        Ok((parse_range(l)?, parse_range(r)?))
+    })
+}
+
+/// One elf's sections, from the pair on line `line` (from 1); `elf` is 0 for
+/// the left range and 1 for the right.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Assignment {
+    pub line: usize,
+    pub elf: usize,
+    pub sections: RangeInclusive<i32>,
+}
+
+pub fn assignments(inp: &[(RangeInclusive<i32>, RangeInclusive<i32>)]) -> Vec<Assignment> {
+    inp.iter()
+        .enumerate()
+        .flat_map(|(i, (l, r))| {
+            [l, r]
+                .into_iter()
+                .enumerate()
+                .map(move |(elf, sections)| Assignment {
+                    line: i + 1,
+                    elf,
+                    sections: sections.clone(),
+                })
+        })
+        .collect()
+}
+
+/// The most elves any section is assigned to, and every section with that
+/// many elves. `None` if no sections are assigned.
+pub fn most_covered(
+    inp: &[(RangeInclusive<i32>, RangeInclusive<i32>)],
+) -> Option<(usize, IntervalSet<i32>)> {
+    // Sweep over the points where the number of elves changes.
+    let mut events: Vec<(i64, isize)> = assignments(inp)
+        .into_iter()
+        .filter(|assignment| !assignment.sections.is_empty())
+        .flat_map(|Assignment { sections, .. }| {
+            [
+                (i64::from(*sections.start()), 1),
+                (i64::from(*sections.end()) + 1, -1),
+            ]
+        })
+        .collect();
+    events.sort_unstable();
+
+    let mut best: Option<(usize, IntervalSet<i32>)> = None;
+    let mut elves = 0;
+    for (i, &(at, change)) in events.iter().enumerate() {
+        elves += change;
+        let Some(&(next, _)) = events.get(i + 1) else {
+            break;
+        };
+        if next == at || elves == 0 {
+            continue;
+        }
+        // Events only come from `i32` sections, so everything before `next` fits.
+        let span = at as i32..=(next - 1) as i32;
+        let count = elves as usize;
+        match &mut best {
+            Some((most, sections)) if *most == count => sections.insert(span),
+            Some((most, _)) if *most > count => {}
+            _ => best = Some((count, IntervalSet::from(span))),
+        }
+    }
+    best
+}
+
+/// The sections between the lowest and highest assigned ones that nobody is
+/// assigned to.
+pub fn uncovered(inp: &[(RangeInclusive<i32>, RangeInclusive<i32>)]) -> Vec<RangeInclusive<i32>> {
+    let covered: IntervalSet<i32> = inp
+        .iter()
+        .flat_map(|(l, r)| [l.clone(), r.clone()])
+        .collect();
+    covered.gaps().collect()
+}
+
+/// A graph over every elf's [`Assignment`], in the order [`assignments`]
+/// returns them, with an edge between each two that share a section.
+pub fn overlap_graph(
+    inp: &[(RangeInclusive<i32>, RangeInclusive<i32>)],
+) -> UnGraph<Assignment, ()> {
+    let mut graph = UnGraph::new_undirected();
+    let mut nodes: Vec<NodeIndex> = assignments(inp)
+        .into_iter()
+        .filter(|assignment| !assignment.sections.is_empty())
+        .map(|assignment| graph.add_node(assignment))
+        .collect();
+    nodes.sort_by_key(|&node| *graph[node].sections.start());
+
+    // Sweep left to right, keeping the assignments that are still open. Each
+    // one that starts overlaps exactly the open ones it hasn't passed the end of.
+    let mut open: Vec<NodeIndex> = Vec::new();
+    for node in nodes {
+        let start = *graph[node].sections.start();
+        open.retain(|&other| *graph[other].sections.end() >= start);
+        for &other in &open {
+            graph.add_edge(other, node, ());
+        }
+        open.push(node);
+    }
+    graph
+}
+
+/// Assignments whose sections all belong to some other single assignment,
+/// on any line. Of identical assignments, only the first is kept.
+pub fn redundant(inp: &[(RangeInclusive<i32>, RangeInclusive<i32>)]) -> Vec<Assignment> {
+    let graph = overlap_graph(inp);
+    let covers = |outer: NodeIndex, inner: NodeIndex| {
+        let (outer, inner) = (&graph[outer].sections, &graph[inner].sections);
+        IntervalSet::from(outer.clone()).contains_range(inner.clone())
+    };
+    graph
+        .node_indices()
+        .filter(|&node| {
+            graph.neighbors(node).any(|other| {
+                covers(other, node) && (!covers(node, other) || other.index() < node.index())
+            })
+        })
+        .map(|node| graph[node].clone())
+        .collect()
+}
+
+#[aoc(day4, part1)]
+pub fn pt1(inp: &[(RangeInclusive<i32>, RangeInclusive<i32>)]) -> usize {
+    inp.iter()
+        .filter(|(l, r)| {
+            IntervalSet::from(l.clone()).contains_range(r.clone())
+                || IntervalSet::from(r.clone()).contains_range(l.clone())
+        })
+        .count()
+}
+
+#[aoc(day4, part2)]
+pub fn pt2(inp: &[(RangeInclusive<i32>, RangeInclusive<i32>)]) -> usize {
+    inp.iter()
+        .filter(|(l, r)| IntervalSet::from(l.clone()).overlaps(r.clone()))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(pt1(&parse(EXAMPLE).unwrap()), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(pt2(&parse(EXAMPLE).unwrap()), 4);
+    }
+
+    #[test]
+    fn analytics_example() {
+        let pairs = parse(EXAMPLE).unwrap();
+        assert_eq!(most_covered(&pairs), Some((8, IntervalSet::from(6..=6))));
+        assert_eq!(uncovered(&pairs), []);
+        assert_eq!(overlap_graph(&pairs).edge_count(), 49);
+        let redundant: Vec<(usize, usize)> = redundant(&pairs)
+            .into_iter()
+            .map(|assignment| (assignment.line, assignment.elf))
+            .collect();
+        // Only 7-9 and 2-8 stick out past every other assignment.
+        assert_eq!(
+            redundant,
+            [
+                (1, 0),
+                (1, 1),
+                (2, 0),
+                (2, 1),
+                (3, 0),
+                (4, 1),
+                (5, 0),
+                (5, 1),
+                (6, 0),
+                (6, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn gaps_and_ties() {
+        let pairs = parse("1-2,8-9\n4-4,8-8").unwrap();
+        assert_eq!(uncovered(&pairs), [3..=3, 5..=7]);
+        assert_eq!(most_covered(&pairs), Some((2, IntervalSet::from(8..=8))));
+        let graph = overlap_graph(&pairs);
+        assert_eq!(graph.edge_count(), 1);
+        assert_eq!(most_covered(&[]), None);
+    }
+}
@@ -0,0 +1,432 @@
+use std::iter::Iterator as _;
+use std::{fmt, ops::RangeInclusive};
+
+use anyhow::{anyhow, bail, Result};
+use regex::Regex;
+use tracing::debug;
+
+use itertools::Itertools;
+
+use crate::parsing::{self, number};
+
+/// Move `count` crates from stack `from` to stack `to`, numbering stacks from 1.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Move {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// A model of crane, deciding which moves it can make and what order the
+/// crates it moves end up in.
+pub trait Crane {
+    /// Rejects moves the crane can't make. Any crane can make any move by default.
+    fn check(&self, _mv: &Move) -> Result<()> {
+        Ok(())
+    }
+
+    /// Puts `crates`, lifted off a stack bottom first, into the order they
+    /// end up in on the destination stack, bottom first.
+    fn arrange(&self, crates: &mut [String]);
+}
+
+/// Moves one crate at a time, so the moved crates end up reversed.
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn arrange(&self, crates: &mut [String]) {
+        crates.reverse();
+    }
+}
+
+/// Moves all the crates at once, keeping their order.
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn arrange(&self, _crates: &mut [String]) {}
+}
+
+/// Crate stacks, bottom first, that remember the moves applied to them so
+/// they can be undone.
+#[derive(Clone, Debug)]
+pub struct Stacks {
+    crates: Vec<Vec<String>>,
+    /// Each applied move, with the crates it lifted in their original order.
+    history: Vec<(Move, Vec<String>)>,
+}
+
+impl Stacks {
+    pub fn new(crates: Vec<Vec<String>>) -> Stacks {
+        Stacks {
+            crates,
+            history: Vec::new(),
+        }
+    }
+
+    /// The stacks, each from the bottom up.
+    pub fn crates(&self) -> &[Vec<String>] {
+        &self.crates
+    }
+
+    /// The crate on top of each stack, skipping empty stacks.
+    pub fn tops(&self) -> String {
+        self.crates.iter().filter_map(|stack| stack.last()).join("")
+    }
+
+    /// Makes `mv` with `crane`, or leaves the stacks alone if the move names
+    /// a missing stack, takes more crates than there are, or the crane refuses.
+    pub fn apply(&mut self, crane: &impl Crane, mv: &Move) -> Result<()> {
+        let &Move { count, from, to } = mv;
+        for stack in [from, to] {
+            if stack == 0 || stack > self.crates.len() {
+                bail!("no stack {stack}");
+            }
+        }
+        let available = self.crates[from - 1].len();
+        if count > available {
+            bail!("can't move {count} crates from stack {from}, which has {available}");
+        }
+        crane.check(mv)?;
+
+        let lifted = self.crates[from - 1].split_off(available - count);
+        let mut moved = lifted.clone();
+        crane.arrange(&mut moved);
+        self.crates[to - 1].extend(moved);
+        self.history.push((*mv, lifted));
+        Ok(())
+    }
+
+    /// Takes back the last applied move, returning it, or `None` if there is
+    /// nothing left to undo.
+    pub fn undo(&mut self) -> Option<Move> {
+        let (mv, lifted) = self.history.pop()?;
+        let to = &mut self.crates[mv.to - 1];
+        to.truncate(to.len() - mv.count);
+        self.crates[mv.from - 1].extend(lifted);
+        Some(mv)
+    }
+
+    /// The states after each of `moves` in turn, stopping after the first
+    /// move that fails.
+    pub fn steps<'a, C: Crane>(&self, crane: &'a C, moves: &'a [Move]) -> Steps<'a, C> {
+        Steps {
+            stacks: Some(self.clone()),
+            crane,
+            moves: moves.iter(),
+        }
+    }
+}
+
+/// The iterator returned by [`Stacks::steps`].
+pub struct Steps<'a, C> {
+    /// `None` once a move has failed.
+    stacks: Option<Stacks>,
+    crane: &'a C,
+    moves: std::slice::Iter<'a, Move>,
+}
+
+impl<C: Crane> Iterator for Steps<'_, C> {
+    type Item = Result<Stacks>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let stacks = self.stacks.as_mut()?;
+        let mv = self.moves.next()?;
+        Some(match stacks.apply(self.crane, mv) {
+            Ok(()) => Ok(stacks.clone()),
+            Err(err) => {
+                self.stacks = None;
+                Err(err)
+            }
+        })
+    }
+}
+
+/// The runs of non-space characters in `text`, with the char columns they span.
+fn words(text: &str) -> Vec<(RangeInclusive<usize>, String)> {
+    let mut words: Vec<(RangeInclusive<usize>, String)> = Vec::new();
+    let mut last_column = None;
+    for (column, c) in text.chars().enumerate() {
+        if c.is_whitespace() {
+            continue;
+        }
+        match words.last_mut() {
+            Some((span, word)) if last_column == Some(column - 1) => {
+                *span = *span.start()..=column;
+                word.push(c);
+            }
+            _ => words.push((column..=column, c.to_string())),
+        }
+        last_column = Some(column);
+    }
+    words
+}
+
+/// Reads the crate drawing: rows of `[label]` crates above a row of stack
+/// numbers. Each crate belongs to the stack whose number it sits over, so
+/// stacks can be any width and there can be any number of them.
+fn parse_drawing(drawing: &[parsing::Line]) -> Result<Stacks> {
+    let (numbers, rows) = drawing
+        .split_last()
+        .ok_or_else(|| anyhow!("day 5: missing crate drawing"))?;
+    let columns: Vec<RangeInclusive<usize>> = numbers.parse(|text| {
+        let words = words(text);
+        if words.is_empty() {
+            bail!("expected stack numbers");
+        }
+        words
+            .into_iter()
+            .enumerate()
+            .map(|(i, (span, word))| {
+                if word != (i + 1).to_string() {
+                    bail!("expected stack {}, found {word:?}", i + 1);
+                }
+                Ok(span)
+            })
+            .collect()
+    })?;
+    debug!(stacks = columns.len(), "read crate drawing");
+
+    let mut stacks: Vec<Vec<String>> = vec![Vec::new(); columns.len()];
+    for (height, row) in rows.iter().rev().enumerate() {
+        row.parse(|text| {
+            for (span, word) in words(text) {
+                let label = word
+                    .strip_prefix('[')
+                    .and_then(|word| word.strip_suffix(']'))
+                    .filter(|label| !label.is_empty())
+                    .ok_or_else(|| anyhow!("expected a crate like `[A]`, found {word:?}"))?;
+                let mut under = columns.iter().positions(|column| {
+                    column.start() <= span.end() && span.start() <= column.end()
+                });
+                let (Some(stack), None) = (under.next(), under.next()) else {
+                    bail!("crate {word} isn't over exactly one stack number");
+                };
+                if stacks[stack].len() != height {
+                    bail!("crate {word} isn't resting on stack {}", stack + 1);
+                }
+                stacks[stack].push(label.to_owned());
+            }
+            Ok(())
+        })?;
+    }
+    Ok(Stacks::new(stacks))
+}
+
+/// Draws the stacks the way the puzzle input does, with each stack centred in
+/// a column wide enough for its widest crate and its number.
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let crates = &self.crates;
+        let width = crates
+            .iter()
+            .flatten()
+            .map(|label| label.chars().count() + 2)
+            .chain([3, crates.len().to_string().len()])
+            .max()
+            .unwrap();
+        let centred = |text: &str| {
+            let len = text.chars().count();
+            let left = (width - len).div_ceil(2);
+            format!(
+                "{}{text}{}",
+                " ".repeat(left),
+                " ".repeat(width - len - left)
+            )
+        };
+        let height = crates.iter().map(Vec::len).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            let row = crates
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(label) => centred(&format!("[{label}]")),
+                    None => " ".repeat(width),
+                })
+                .join(" ");
+            writeln!(f, "{row}")?;
+        }
+        let numbers = (1..=crates.len())
+            .map(|number| centred(&number.to_string()))
+            .join(" ");
+        write!(f, "{numbers}")
+    }
+}
+
+/// The starting crate stacks and the moves to make.
+pub type Input = (Stacks, Vec<Move>);
+
+#[aoc_generator(day5)]
+pub fn parse(input: &str) -> Result<Input> {
+    let mut lines = parsing::lines(5, input);
+    let drawing = lines
+        .by_ref()
+        .take_while(|line| !line.text.trim().is_empty())
+        .collect::<Vec<_>>();
+    let starting_stacks = parse_drawing(&drawing)?;
+    // Replay the moves as they're read, so that a move that can't be made is
+    // reported against its line. Both cranes can make the same moves.
+    let mut replay = starting_stacks.clone();
+    let instruction_pattern = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+    let instructions = lines
+        .map(|line| {
+            line.parse(|text| {
+                let caps = instruction_pattern
+                    .captures(text.trim_end())
+                    .ok_or_else(|| anyhow!("expected `move N from A to B`"))?;
+                let mv = Move {
+                    count: number(&caps[1])?,
+                    from: number(&caps[2])?,
+                    to: number(&caps[3])?,
+                };
+                replay.apply(&CrateMover9000, &mv)?;
+                Ok(mv)
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok((starting_stacks, instructions))
+}
+
+fn run(crane: &impl Crane, (starting_stacks, instructions): &Input) -> String {
+    let mut stacks = starting_stacks.clone();
+    for mv in instructions {
+        stacks.apply(crane, mv).expect("parse replays every move");
+    }
+    stacks.tops()
+}
+
+#[aoc(day5, part1)]
+pub fn pt1(input: &Input) -> String {
+    run(&CrateMover9000, input)
+}
+
+#[aoc(day5, part2)]
+pub fn pt2(input: &Input) -> String {
+    run(&CrateMover9001, input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(pt1(&parse(EXAMPLE).unwrap()), "CMZ");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(pt2(&parse(EXAMPLE).unwrap()), "MCD");
+    }
+
+    /// A crane that can only lift so many crates at once.
+    struct Limited(usize);
+
+    impl Crane for Limited {
+        fn check(&self, mv: &Move) -> Result<()> {
+            if mv.count > self.0 {
+                bail!("can't lift {} crates", mv.count);
+            }
+            Ok(())
+        }
+
+        fn arrange(&self, _crates: &mut [String]) {}
+    }
+
+    #[test]
+    fn steps_and_undo() {
+        let (start, moves) = parse(EXAMPLE).unwrap();
+        let states: Vec<Stacks> = start
+            .steps(&CrateMover9000, &moves)
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(
+            states.iter().map(Stacks::tops).collect::<Vec<_>>(),
+            ["DCP", "CZ", "MZ", "CMZ"]
+        );
+
+        let mut stacks = states[3].clone();
+        for (i, mv) in moves.iter().enumerate().rev() {
+            assert_eq!(stacks.undo(), Some(*mv));
+            let before = if i == 0 { &start } else { &states[i - 1] };
+            assert_eq!(stacks.crates(), before.crates());
+        }
+        assert_eq!(stacks.undo(), None);
+    }
+
+    #[test]
+    fn bad_moves_are_errors() {
+        let (mut stacks, _) = parse(EXAMPLE).unwrap();
+        let all_of_two = Move {
+            count: 3,
+            from: 2,
+            to: 1,
+        };
+        let too_many = Move {
+            count: 4,
+            ..all_of_two
+        };
+        assert!(stacks.apply(&CrateMover9001, &too_many).is_err());
+        assert!(stacks.apply(&Limited(2), &all_of_two).is_err());
+        assert_eq!(stacks.crates(), parse(EXAMPLE).unwrap().0.crates());
+
+        let mut failing = stacks.steps(&CrateMover9000, std::slice::from_ref(&too_many));
+        assert!(failing.next().unwrap().is_err());
+        assert!(failing.next().is_none());
+
+        let err = parse(&EXAMPLE.replace("move 3 from 1", "move 4 from 1")).unwrap_err();
+        assert!(err.to_string().starts_with("day 5, line 7"), "{err}");
+    }
+
+    #[test]
+    fn drawing_round_trips() {
+        let (stacks, _) = parse(EXAMPLE).unwrap();
+        let drawing = EXAMPLE.split("\n\n").next().unwrap();
+        assert_eq!(stacks.to_string(), drawing);
+
+        let wide = Stacks::new(
+            (1..=12)
+                .map(|i| (0..i % 4).map(|j| format!("{i}{j}")).collect())
+                .collect(),
+        );
+        let drawn = wide.to_string();
+        assert!(drawn.ends_with(" 10    11    12 "), "{drawn}");
+        let (parsed, moves) = parse(&format!("{drawn}\n\nmove 2 from 11 to 10")).unwrap();
+        assert_eq!(parsed.crates(), wide.crates());
+        assert_eq!(
+            moves,
+            [Move {
+                count: 2,
+                from: 11,
+                to: 10
+            }]
+        );
+    }
+
+    #[test]
+    fn ragged_drawing() {
+        let (stacks, _) = parse("[A]\n[B] [C]  \n 1   2   3\n   \nmove 1 from 1 to 3  ").unwrap();
+        assert_eq!(stacks.crates(), [vec!["B", "A"], vec!["C"], vec![]]);
+
+        for (bad, line) in [
+            ("[A]\n 1 3\n\nmove 1 from 1 to 1", 2),
+            ("[A]\n    [B]\n 1   2\n\nmove 1 from 2 to 1", 1),
+            ("[A] X\n 1   2\n\nmove 1 from 1 to 2", 1),
+            ("[A]\n 1\n\nmove 1 from 1 to 2", 4),
+        ] {
+            let err = parse(bad).unwrap_err();
+            assert!(
+                err.to_string().starts_with(&format!("day 5, line {line}")),
+                "{err}"
+            );
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    fmt,
+    io::{self, BufReader, Read},
+};
+
+use anyhow::{bail, Result};
+
+use crate::parsing;
+
+/// The last `len` bytes of a stream, with a count of each byte value so that
+/// checking whether they are all different takes constant time.
+pub struct Window {
+    len: usize,
+    /// The bytes in the window, oldest at `next` once it has filled up.
+    ring: Vec<u8>,
+    next: usize,
+    counts: [usize; 256],
+    /// How many byte values appear more than once in the window.
+    repeated: usize,
+}
+
+impl Window {
+    /// A window over the last `len` bytes.
+    ///
+    /// # Panics
+    ///
+    /// If `len` is zero, since an empty window can't hold a marker.
+    pub fn new(len: usize) -> Window {
+        assert!(len > 0, "a marker window needs at least one byte");
+        Window {
+            len,
+            ring: Vec::with_capacity(len),
+            next: 0,
+            counts: [0; 256],
+            repeated: 0,
+        }
+    }
+
+    /// Slides the window on by `byte`, returning whether it now holds `len`
+    /// different bytes.
+    pub fn push(&mut self, byte: u8) -> bool {
+        if self.ring.len() < self.len {
+            self.ring.push(byte);
+        } else {
+            let old = std::mem::replace(&mut self.ring[self.next], byte);
+            self.counts[old as usize] -= 1;
+            if self.counts[old as usize] == 1 {
+                self.repeated -= 1;
+            }
+            self.next = (self.next + 1) % self.len;
+        }
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] == 2 {
+            self.repeated += 1;
+        }
+        self.ring.len() == self.len && self.repeated == 0
+    }
+}
+
+/// The markers of `window` different characters in `stream`, as the number
+/// of characters read up to the end of each, read only as far as they are
+/// asked for. Line endings are skipped. `window` must not be 0.
+fn markers(stream: impl Read, window: usize) -> impl Iterator<Item = io::Result<usize>> {
+    let mut recent = Window::new(window);
+    let mut read = 0;
+    BufReader::new(stream)
+        .bytes()
+        .filter_map(move |byte| match byte {
+            Err(err) => Some(Err(err)),
+            Ok(b'\n' | b'\r') => None,
+            Ok(byte) => {
+                read += 1;
+                recent.push(byte).then_some(Ok(read))
+            }
+        })
+}
+
+/// Finds every marker of `window` different characters in `stream`, as the
+/// number of characters read up to the end of each. Line endings are skipped.
+pub fn find_marker(stream: impl Read, window: usize) -> Result<Vec<usize>> {
+    if window == 0 {
+        bail!("day 6: markers need at least one character");
+    }
+    let markers = markers(stream, window).collect::<io::Result<Vec<_>>>()?;
+    if markers.is_empty() {
+        bail!("day 6: no marker of {window} different characters");
+    }
+    Ok(markers)
+}
+
+/// Like [`find_marker`], but stops reading at the first marker.
+pub fn first_marker(stream: impl Read, window: usize) -> Result<usize> {
+    if window == 0 {
+        bail!("day 6: markers need at least one character");
+    }
+    match markers(stream, window).next() {
+        Some(marker) => Ok(marker?),
+        None => bail!("day 6: no marker of {window} different characters"),
+    }
+}
+
+/// A marker and the payload after it, up to the start of the next marker or
+/// the end of the stream.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Frame<'a> {
+    /// Where the marker starts in the stream.
+    pub offset: usize,
+    pub marker_len: usize,
+    pub payload: &'a [u8],
+}
+
+/// Splits a datastream into [`Frame`]s. Each marker is looked for only after
+/// the end of the one before, so markers never overlap.
+pub struct Frames<'a> {
+    data: &'a [u8],
+    marker_len: usize,
+    /// Where the next frame's marker ends, if there is one.
+    next_marker_end: Option<usize>,
+}
+
+/// Where the first marker of `marker_len` different bytes at or after `from` ends.
+fn marker_end(data: &[u8], from: usize, marker_len: usize) -> Option<usize> {
+    let mut recent = Window::new(marker_len);
+    data[from..]
+        .iter()
+        .position(|&byte| recent.push(byte))
+        .map(|i| from + i + 1)
+}
+
+pub fn frames(data: &[u8], marker_len: usize) -> Frames<'_> {
+    Frames {
+        data,
+        marker_len,
+        next_marker_end: (marker_len > 0)
+            .then(|| marker_end(data, 0, marker_len))
+            .flatten(),
+    }
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = Frame<'a>;
+
+    fn next(&mut self) -> Option<Frame<'a>> {
+        let start = self.next_marker_end?;
+        self.next_marker_end = marker_end(self.data, start, self.marker_len);
+        let end = self
+            .next_marker_end
+            .map_or(self.data.len(), |end| end - self.marker_len);
+        Some(Frame {
+            offset: start - self.marker_len,
+            marker_len: self.marker_len,
+            payload: &self.data[start..end],
+        })
+    }
+}
+
+/// An overview of a whole transmission split into frames.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Summary {
+    pub marker_len: usize,
+    pub frames: usize,
+    /// Bytes before the first marker, or the whole stream if there is none.
+    pub preamble: usize,
+    pub payload_bytes: usize,
+    pub longest_payload: usize,
+}
+
+pub fn summarize(data: &[u8], marker_len: usize) -> Summary {
+    let mut summary = Summary {
+        marker_len,
+        frames: 0,
+        preamble: data.len(),
+        payload_bytes: 0,
+        longest_payload: 0,
+    };
+    for frame in frames(data, marker_len) {
+        summary.preamble = summary.preamble.min(frame.offset);
+        summary.frames += 1;
+        summary.payload_bytes += frame.payload.len();
+        summary.longest_payload = summary.longest_payload.max(frame.payload.len());
+    }
+    summary
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Summary {
+            marker_len,
+            frames,
+            preamble,
+            payload_bytes,
+            longest_payload,
+        } = self;
+        write!(
+            f,
+            "{frames} frames with {marker_len}-byte markers after {preamble} bytes of preamble, \
+             carrying {payload_bytes} payload bytes (longest {longest_payload})"
+        )
+    }
+}
+
+pub const START_OF_PACKET: usize = 4;
+pub const START_OF_MESSAGE: usize = 14;
+
+#[aoc_generator(day6)]
+pub fn parse(input: &str) -> Result<String> {
+    parsing::single_line(6, input)?.parse(|text| {
+        if let Some(c) = text.chars().find(|c| !c.is_ascii_lowercase()) {
+            bail!("unexpected character {c:?} in datastream");
+        }
+        // Any start-of-message marker also ends with a start-of-packet one.
+        first_marker(text.as_bytes(), START_OF_MESSAGE)?;
+        Ok(text.to_owned())
+    })
+}
+
+#[aoc(day6, part1)]
+pub fn pt1(input: &str) -> usize {
+    first_marker(input.as_bytes(), START_OF_PACKET).expect("parse checks for a marker")
+}
+
+#[aoc(day6, part2)]
+pub fn pt2(input: &str) -> usize {
+    first_marker(input.as_bytes(), START_OF_MESSAGE).expect("parse checks for a marker")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: [(&str, usize, usize); 5] = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    fn part1_examples() {
+        for (example, start_of_packet, _) in EXAMPLES {
+            assert_eq!(pt1(&parse(example).unwrap()), start_of_packet, "{example}");
+        }
+    }
+
+    #[test]
+    fn part2_examples() {
+        for (example, _, start_of_message) in EXAMPLES {
+            assert_eq!(pt2(&parse(example).unwrap()), start_of_message, "{example}");
+        }
+    }
+
+    #[test]
+    fn every_marker() {
+        assert_eq!(find_marker("abcabd\n".as_bytes(), 3).unwrap(), [3, 4, 5, 6]);
+        assert_eq!(find_marker("aabbaab".as_bytes(), 2).unwrap(), [3, 5, 7]);
+        assert_eq!(find_marker("aaa".as_bytes(), 1).unwrap(), [1, 2, 3]);
+        assert!(find_marker("abcabc".as_bytes(), 4).is_err());
+        assert!(find_marker("abc".as_bytes(), 0).is_err());
+        assert_eq!(first_marker("abcabd\n".as_bytes(), 3).unwrap(), 3);
+        assert!(first_marker("abcabc".as_bytes(), 4).is_err());
+        assert!(first_marker("abc".as_bytes(), 0).is_err());
+        assert!(parse("abcabcabcabcabcabc").is_err());
+    }
+
+    #[test]
+    fn frames_and_summary() {
+        let data = b"aaaabcdxxxxefghyy";
+        let frame = |offset, payload| Frame {
+            offset,
+            marker_len: 4,
+            payload,
+        };
+        assert_eq!(
+            frames(data, 4).collect::<Vec<_>>(),
+            [frame(3, &b"xxx"[..]), frame(10, &b"hyy"[..])]
+        );
+        let summary = summarize(data, 4);
+        assert_eq!(
+            summary,
+            Summary {
+                marker_len: 4,
+                frames: 2,
+                preamble: 3,
+                payload_bytes: 6,
+                longest_payload: 3,
+            }
+        );
+        assert_eq!(
+            summary.to_string(),
+            "2 frames with 4-byte markers after 3 bytes of preamble, \
+             carrying 6 payload bytes (longest 3)"
+        );
+
+        let (example, _, start_of_message) = EXAMPLES[0];
+        let message = frames(example.as_bytes(), START_OF_MESSAGE).next().unwrap();
+        assert_eq!(message.offset + START_OF_MESSAGE, start_of_message);
+        assert_eq!(message.payload, b"jfqwrcgsmlb");
+        assert_eq!(summarize(b"aaaa", 2).frames, 0);
+        assert_eq!(summarize(b"aaaa", 2).preamble, 4);
+        assert_eq!(frames(b"abc", 0).next(), None);
+    }
+
+    #[test]
+    fn windows_slide() {
+        let mut window = Window::new(1);
+        assert!(window.push(b'a') && window.push(b'a'));
+        let mut window = Window::new(3);
+        assert!(!window.push(b'a') && !window.push(b'b') && window.push(b'c'));
+        assert!(!window.push(b'b') && window.push(b'a'));
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one byte")]
+    fn empty_windows_are_rejected() {
+        Window::new(0);
+    }
+}
@@ -0,0 +1,661 @@
+use std::{collections::BTreeMap, error::Error, fmt};
+
+use anyhow::{bail, Context, Result};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{digit1, space1},
+    combinator::{all_consuming, eof, map_res, rest, verify},
+    sequence::terminated,
+    IResult, Parser,
+};
+
+use serde_json::{json, Value};
+
+use crate::parsing;
+
+#[derive(Clone, Debug)]
+pub enum FileEntry {
+    Dir(String),
+    File(i64, String),
+}
+
+#[derive(Clone, Debug)]
+pub enum Command {
+    Ls,
+    Cd(Cd),
+}
+
+#[derive(Clone, Debug)]
+pub enum Cd {
+    Out,
+    In(String),
+    Root,
+}
+
+#[derive(Clone, Debug)]
+pub enum InputLine {
+    Cmd(Command),
+    FileEntry(FileEntry),
+}
+
+/// A name in a directory listing: anything but a path.
+fn name(input: &str) -> IResult<&str, String> {
+    verify(rest, |name: &str| !name.is_empty() && !name.contains('/'))
+        .map(str::to_owned)
+        .parse(input)
+}
+
+fn cd(input: &str) -> IResult<&str, Cd> {
+    let (input, _) = tag("cd ")(input)?;
+    alt((
+        terminated(tag(".."), eof).map(|_| Cd::Out),
+        terminated(tag("/"), eof).map(|_| Cd::Root),
+        name.map(Cd::In),
+    ))(input)
+}
+
+fn ls(input: &str) -> IResult<&str, ()> {
+    terminated(tag("ls"), eof).map(|_| ()).parse(input)
+}
+
+fn cmd(input: &str) -> IResult<&str, Command> {
+    let (input, _) = tag("$ ")(input)?;
+    alt((ls.map(|()| Command::Ls), cd.map(Command::Cd)))(input)
+}
+
+fn dir(input: &str) -> IResult<&str, String> {
+    let (input, _) = tag("dir ")(input)?;
+    name(input)
+}
+
+fn file(input: &str) -> IResult<&str, (i64, String)> {
+    let (input, size) = map_res(digit1, |s: &str| s.parse::<i64>())(input)?;
+    let (input, _) = space1(input)?;
+    let (input, name) = name(input)?;
+    Ok((input, (size, name)))
+}
+
+fn file_entry(input: &str) -> IResult<&str, FileEntry> {
+    alt((
+        dir.map(FileEntry::Dir),
+        file.map(|(size, name)| FileEntry::File(size, name)),
+    ))(input)
+}
+
+fn input_line(line: &str) -> Result<InputLine> {
+    if let Ok((_, command)) = all_consuming(cmd)(line) {
+        Ok(InputLine::Cmd(command))
+    } else if let Ok((_, file_entry)) = all_consuming(file_entry)(line) {
+        Ok(InputLine::FileEntry(file_entry))
+    } else {
+        bail!("not a command or a directory listing entry")
+    }
+}
+
+/// Parses the terminal session line by line.
+pub fn terminal(input: &str) -> Result<Vec<InputLine>> {
+    parsing::parse_lines(7, input, input_line)
+}
+
+/// Parses and replays the session together, so that a line that can't happen
+/// on a real disk is reported with its text like any other parse error.
+#[aoc_generator(day7)]
+pub fn parse(input: &str) -> Result<FileSystem> {
+    let mut replay = Replay::default();
+    for line in parsing::lines(7, input) {
+        line.parse(|text| Ok(replay.step(&input_line(text)?)?))?;
+    }
+    Ok(replay.finish())
+}
+
+/// A file or directory. Directories carry a `Size` once it has been worked out.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Node<Size = ()> {
+    Dir(BTreeMap<String, Node<Size>>, Size),
+    Leaf(i64),
+}
+
+impl Default for Node {
+    fn default() -> Self {
+        Node::Dir(BTreeMap::new(), ())
+    }
+}
+
+impl Node {
+    fn compute_sizes(self) -> Node<i64> {
+        match self {
+            Node::Dir(dir, ()) => {
+                let mut size = 0;
+                let dir = dir
+                    .into_iter()
+                    .map(|(name, node)| {
+                        let node = node.compute_sizes();
+                        size += node.size();
+                        (name, node)
+                    })
+                    .collect();
+                Node::Dir(dir, size)
+            }
+            Node::Leaf(size) => Node::Leaf(size),
+        }
+    }
+}
+
+impl<Size> Node<Size> {
+    pub fn is_dir(&self) -> bool {
+        matches!(self, Node::Dir(..))
+    }
+
+    /// The entries of a directory by name, or nothing for a file.
+    pub fn children(&self) -> impl Iterator<Item = (&str, &Node<Size>)> {
+        let entries = match self {
+            Node::Dir(dir, _) => Some(dir.iter().map(|(name, node)| (name.as_str(), node))),
+            Node::Leaf(_) => None,
+        };
+        entries.into_iter().flatten()
+    }
+}
+
+impl Node<i64> {
+    /// The size of a file, or the total size of everything in a directory.
+    pub fn size(&self) -> i64 {
+        match self {
+            Node::Dir(_, size) => *size,
+            Node::Leaf(size) => *size,
+        }
+    }
+}
+
+/// The directory at `path`, which replaying the session has already checked
+/// is made of listed directories.
+fn listing<'a>(root: &'a mut Node, path: &[String]) -> &'a mut BTreeMap<String, Node> {
+    let mut node = root;
+    for name in path {
+        let Node::Dir(dir, ()) = node else {
+            unreachable!("cd only enters directories")
+        };
+        node = dir
+            .get_mut(name)
+            .expect("cd only enters listed directories");
+    }
+    let Node::Dir(dir, ()) = node else {
+        unreachable!("cd only enters directories")
+    };
+    dir
+}
+
+/// Something in a terminal session that doesn't make sense for a real disk.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Diagnostic {
+    /// `cd` into a name the current directory never listed.
+    UnlistedDir(String),
+    /// `cd` into a name listed as a file.
+    NotADir(String),
+    /// `cd ..` from the root.
+    AboveRoot,
+    /// A file listed again with a different size.
+    SizeChanged { name: String, was: i64, now: i64 },
+    /// A name listed as both a file and a directory.
+    KindChanged(String),
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::UnlistedDir(name) => write!(f, "cd into {name}, which was never listed"),
+            Diagnostic::NotADir(name) => write!(f, "cd into {name}, which is a file"),
+            Diagnostic::AboveRoot => write!(f, "cd .. from the root"),
+            Diagnostic::SizeChanged { name, was, now } => {
+                write!(f, "{name} listed with size {now} after size {was}")
+            }
+            Diagnostic::KindChanged(name) => {
+                write!(f, "{name} listed as both a file and a directory")
+            }
+        }
+    }
+}
+
+impl Error for Diagnostic {}
+
+/// The directory tree pieced together from a terminal session, with every
+/// directory's total size worked out once up front.
+pub struct FileSystem {
+    root: Node<i64>,
+}
+
+/// The tree so far and the working directory, while a session is replayed.
+#[derive(Default)]
+struct Replay {
+    root: Node,
+    cwd: Vec<String>,
+}
+
+impl Replay {
+    fn step(&mut self, line: &InputLine) -> Result<(), Diagnostic> {
+        let Replay { root, cwd } = self;
+        match line {
+            InputLine::Cmd(Command::Ls) => {}
+            InputLine::Cmd(Command::Cd(Cd::Out)) => {
+                cwd.pop().ok_or(Diagnostic::AboveRoot)?;
+            }
+            InputLine::Cmd(Command::Cd(Cd::In(name))) => match listing(root, cwd).get(name) {
+                None => return Err(Diagnostic::UnlistedDir(name.clone())),
+                Some(Node::Leaf(_)) => return Err(Diagnostic::NotADir(name.clone())),
+                Some(Node::Dir(..)) => cwd.push(name.clone()),
+            },
+            InputLine::Cmd(Command::Cd(Cd::Root)) => cwd.clear(),
+            InputLine::FileEntry(FileEntry::Dir(name)) => {
+                if !listing(root, cwd).entry(name.clone()).or_default().is_dir() {
+                    return Err(Diagnostic::KindChanged(name.clone()));
+                }
+            }
+            InputLine::FileEntry(FileEntry::File(size, name)) => {
+                match listing(root, cwd).insert(name.clone(), Node::Leaf(*size)) {
+                    Some(Node::Dir(..)) => return Err(Diagnostic::KindChanged(name.clone())),
+                    Some(Node::Leaf(was)) if was != *size => {
+                        return Err(Diagnostic::SizeChanged {
+                            name: name.clone(),
+                            was,
+                            now: *size,
+                        })
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> FileSystem {
+        FileSystem {
+            root: self.root.compute_sizes(),
+        }
+    }
+}
+
+impl FileSystem {
+    /// Replays the `cd` commands and `ls` listings of a session. Directories
+    /// that are listed but never entered are kept, empty. Fails with the
+    /// [`Diagnostic`] for the first line that can't happen on a real disk.
+    pub fn build(session: &[InputLine]) -> Result<FileSystem> {
+        let mut replay = Replay::default();
+        for (i, line) in session.iter().enumerate() {
+            replay
+                .step(line)
+                .with_context(|| format!("session line {}", i + 1))?;
+        }
+        Ok(replay.finish())
+    }
+
+    pub fn root(&self) -> &Node<i64> {
+        &self.root
+    }
+
+    /// The space used by everything on the disk.
+    pub fn used(&self) -> i64 {
+        self.root.size()
+    }
+
+    /// The node at a `/`-separated path from the root, such as `/a/e`. The
+    /// leading `/` is optional, and `/` on its own is the root.
+    pub fn get(&self, path: &str) -> Option<&Node<i64>> {
+        path.split('/').filter(|name| !name.is_empty()).try_fold(
+            &self.root,
+            |node, name| match node {
+                Node::Dir(dir, _) => dir.get(name),
+                Node::Leaf(_) => None,
+            },
+        )
+    }
+
+    /// Every directory with its full path, parents before their children and
+    /// siblings in name order.
+    pub fn dirs(&self) -> impl Iterator<Item = (String, &Node<i64>)> {
+        let mut stack = vec![("/".to_owned(), &self.root)];
+        std::iter::from_fn(move || {
+            let (path, node) = stack.pop()?;
+            let subdirs: Vec<_> = node
+                .children()
+                .filter(|(_, child)| child.is_dir())
+                .collect();
+            for (name, child) in subdirs.into_iter().rev() {
+                let separator = if path == "/" { "" } else { "/" };
+                stack.push((format!("{path}{separator}{name}"), child));
+            }
+            Some((path, node))
+        })
+    }
+}
+
+/// Which entries the renderers show: nothing deeper than `max_depth` levels
+/// below the root, and nothing smaller than `min_size`. The root is always shown.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RenderOptions {
+    pub max_depth: Option<usize>,
+    pub min_size: i64,
+}
+
+impl RenderOptions {
+    fn shows(&self, depth: usize, node: &Node<i64>) -> bool {
+        depth == 0
+            || (self.max_depth.is_none_or(|max| depth <= max) && node.size() >= self.min_size)
+    }
+}
+
+/// A shown entry, with its full path and how many levels below the root it is.
+struct Entry<'a> {
+    path: String,
+    name: &'a str,
+    depth: usize,
+    node: &'a Node<i64>,
+}
+
+impl FileSystem {
+    /// Every entry the options show, parents before their children and
+    /// siblings in name order.
+    fn entries(&self, options: RenderOptions) -> Vec<Entry<'_>> {
+        let mut entries = Vec::new();
+        let mut stack = vec![Entry {
+            path: "/".to_owned(),
+            name: "/",
+            depth: 0,
+            node: &self.root,
+        }];
+        while let Some(entry) = stack.pop() {
+            let children: Vec<_> = entry
+                .node
+                .children()
+                .filter(|(_, child)| options.shows(entry.depth + 1, child))
+                .collect();
+            for (name, node) in children.into_iter().rev() {
+                let separator = if entry.path == "/" { "" } else { "/" };
+                stack.push(Entry {
+                    path: format!("{}{separator}{name}", entry.path),
+                    name,
+                    depth: entry.depth + 1,
+                    node,
+                });
+            }
+            entries.push(entry);
+        }
+        entries
+    }
+
+    /// Draws the tree the way the puzzle does, with every size filled in:
+    ///
+    /// ```text
+    /// - / (dir, size=48381165)
+    ///   - a (dir, size=94853)
+    ///     - e (dir, size=584)
+    /// ```
+    pub fn tree(&self, options: RenderOptions) -> String {
+        self.entries(options)
+            .iter()
+            .map(|entry| {
+                let kind = if entry.node.is_dir() { "dir" } else { "file" };
+                format!(
+                    "{}- {} ({kind}, size={})\n",
+                    "  ".repeat(entry.depth),
+                    entry.name,
+                    entry.node.size()
+                )
+            })
+            .collect()
+    }
+
+    /// Lists every file and directory like `du -a`, as size and path
+    /// separated by a tab, biggest first and then by path.
+    pub fn du(&self, options: RenderOptions) -> String {
+        let mut entries = self.entries(options);
+        entries.sort_by(|a, b| b.node.size().cmp(&a.node.size()).then(a.path.cmp(&b.path)));
+        entries
+            .iter()
+            .map(|entry| format!("{}\t{}\n", entry.node.size(), entry.path))
+            .collect()
+    }
+
+    /// The tree as nested JSON objects with a `name`, `type` (`"dir"` or
+    /// `"file"`) and `size`, and the `children` of each directory.
+    pub fn to_json(&self, options: RenderOptions) -> Result<String> {
+        fn value(name: &str, depth: usize, node: &Node<i64>, options: RenderOptions) -> Value {
+            match node {
+                Node::Leaf(size) => json!({ "name": name, "type": "file", "size": size }),
+                Node::Dir(_, size) => {
+                    let children: Vec<Value> = node
+                        .children()
+                        .filter(|(_, child)| options.shows(depth + 1, child))
+                        .map(|(name, child)| value(name, depth + 1, child, options))
+                        .collect();
+                    json!({ "name": name, "type": "dir", "size": size, "children": children })
+                }
+            }
+        }
+        Ok(serde_json::to_string_pretty(&value(
+            "/", 0, &self.root, options,
+        ))?)
+    }
+}
+
+const DISK_SIZE: i64 = 70000000;
+const NEEDED: i64 = 30000000;
+
+#[aoc(day7, part1)]
+pub fn pt1(fs: &FileSystem) -> i64 {
+    fs.dirs()
+        .map(|(_, dir)| dir.size())
+        .filter(|&size| size <= 100000)
+        .sum()
+}
+
+#[aoc(day7, part2)]
+pub fn pt2(fs: &FileSystem) -> i64 {
+    let need_free_at_least = NEEDED - (DISK_SIZE - fs.used());
+    fs.dirs()
+        .map(|(_, dir)| dir.size())
+        .filter(|&size| size >= need_free_at_least)
+        .min()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(pt1(&parse(EXAMPLE).unwrap()), 95437);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(pt2(&parse(EXAMPLE).unwrap()), 24933642);
+    }
+
+    #[test]
+    fn file_system_queries() {
+        let fs = parse(&format!(
+            "{EXAMPLE}\n$ ls\ndir e\n$ cd e\n$ cd /\n$ ls\ndir empty"
+        ))
+        .unwrap();
+        assert_eq!(fs.used(), 48381165);
+        assert_eq!(fs.get("/a/e").map(Node::size), Some(584));
+        assert_eq!(fs.get("d/k"), Some(&Node::Leaf(7214296)));
+        assert_eq!(fs.get("/b.txt/x"), None);
+        assert_eq!(fs.get("/empty"), Some(&Node::Dir(BTreeMap::new(), 0)));
+        let dirs: Vec<(String, i64)> = fs.dirs().map(|(path, dir)| (path, dir.size())).collect();
+        assert_eq!(
+            dirs,
+            [
+                ("/".to_owned(), 48381165),
+                ("/a".to_owned(), 94853),
+                ("/a/e".to_owned(), 584),
+                ("/d".to_owned(), 24933642),
+                ("/d/e".to_owned(), 0),
+                ("/empty".to_owned(), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn cd_root_from_anywhere() {
+        let fs =
+            parse("$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\ndir b\n$ cd b\n$ cd /\n$ ls\n5 x").unwrap();
+        assert_eq!(fs.get("/x"), Some(&Node::Leaf(5)));
+        assert_eq!(fs.get("/a/x"), None);
+    }
+
+    #[test]
+    fn replay_diagnostics() {
+        for (session, line, diagnostic) in [
+            ("$ cd /\n$ cd a", 2, Diagnostic::UnlistedDir("a".to_owned())),
+            ("$ ls\n1 a\n$ cd a", 3, Diagnostic::NotADir("a".to_owned())),
+            ("$ cd /\n$ cd ..", 2, Diagnostic::AboveRoot),
+            (
+                "$ ls\n1 a\n$ ls\n2 a",
+                4,
+                Diagnostic::SizeChanged {
+                    name: "a".to_owned(),
+                    was: 1,
+                    now: 2,
+                },
+            ),
+            (
+                "$ ls\n1 a\ndir a",
+                3,
+                Diagnostic::KindChanged("a".to_owned()),
+            ),
+        ] {
+            let err = parse(session).err().unwrap();
+            let err = err.downcast_ref::<parsing::ParseError>().unwrap();
+            assert_eq!(err.line, line);
+            assert_eq!(
+                err.cause.downcast_ref::<Diagnostic>(),
+                Some(&diagnostic),
+                "{err}"
+            );
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "day 7, line {line} ({:?}): {diagnostic}",
+                    session.lines().nth(line - 1).unwrap()
+                )
+            );
+
+            let err = FileSystem::build(&terminal(session).unwrap())
+                .err()
+                .unwrap();
+            assert_eq!(err.downcast_ref::<Diagnostic>(), Some(&diagnostic));
+            assert_eq!(
+                format!("{err:#}"),
+                format!("session line {line}: {diagnostic}")
+            );
+        }
+        assert!(parse("$ ls\n1 a\n$ ls\n1 a").is_ok());
+    }
+
+    #[test]
+    fn renderers() {
+        let fs = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            fs.tree(RenderOptions::default()),
+            "- / (dir, size=48381165)
+  - a (dir, size=94853)
+    - e (dir, size=584)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir, size=24933642)
+    - d.ext (file, size=5626152)
+    - d.log (file, size=8033020)
+    - j (file, size=4060174)
+    - k (file, size=7214296)
+"
+        );
+
+        let shallow = RenderOptions {
+            max_depth: Some(1),
+            min_size: 90000,
+        };
+        assert_eq!(
+            fs.tree(shallow),
+            "- / (dir, size=48381165)
+  - a (dir, size=94853)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir, size=24933642)
+"
+        );
+        assert_eq!(
+            fs.du(shallow),
+            "48381165\t/\n24933642\t/d\n14848514\t/b.txt\n8504156\t/c.dat\n94853\t/a\n"
+        );
+        assert_eq!(fs.du(RenderOptions::default()).lines().count(), 14);
+
+        let json: Value = serde_json::from_str(
+            &fs.to_json(RenderOptions {
+                max_depth: None,
+                min_size: 8000000,
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "name": "/", "type": "dir", "size": 48381165,
+                "children": [
+                    { "name": "b.txt", "type": "file", "size": 14848514 },
+                    { "name": "c.dat", "type": "file", "size": 8504156 },
+                    {
+                        "name": "d", "type": "dir", "size": 24933642,
+                        "children": [{ "name": "d.log", "type": "file", "size": 8033020 }],
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn commands_must_match_the_whole_line() {
+        for (session, line) in [
+            ("$ cd /\n$ cd /a\n$ ls\n5 x", 2),
+            ("$ ls\ndir a\n$ cd a\n$ cd ..x", 4),
+            ("$ lsx", 1),
+            ("$ ls\ndir a/b", 2),
+        ] {
+            let err = parse(session).err().unwrap();
+            assert!(
+                format!("{err:#}").starts_with(&format!("day 7, line {line}")),
+                "{err:#}"
+            );
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+
+use crate::grid::{Grid, GridView};
+
+#[aoc_generator(day8)]
+pub fn parse(input: &str) -> Result<Grid<i32>> {
+    Grid::parse(8, input, |c| {
+        c.to_digit(10)
+            .map(|height| height as i32)
+            .ok_or_else(|| anyhow!("expected a digit, got {c:?}"))
+    })
+}
+
+fn visible_from_left(view: &GridView<i32>) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    for (row, heights) in view.rows().enumerate() {
+        let mut tallest = -1;
+        for (col, &height) in heights.enumerate() {
+            if height > tallest {
+                tallest = height;
+                out.push(view.source_pos((row, col)));
+            }
+        }
+    }
+    out
+}
+
+/// For every tree, how many trees it can see looking left in `view`, keyed by
+/// its position in the underlying grid.
+fn viewing_distance_to_left(view: &GridView<i32>, out: &mut Grid<usize>) {
+    for (row, heights) in view.rows().enumerate() {
+        let mut height_to_last_col: Vec<Option<usize>> = vec![None; 10];
+        for (c, &height) in heights.enumerate() {
+            let height_here: usize = height.try_into().unwrap();
+            assert!(height_here <= 9);
+            let last_col_at_least_as_tall =
+                height_to_last_col[height_here..].iter().flatten().max();
+            out[view.source_pos((row, c))] = match last_col_at_least_as_tall {
+                Some(&col) => c - col,
+                None => c,
+            };
+            height_to_last_col[height_here] = Some(c);
+        }
+    }
+}
+
+#[aoc(day8, part1)]
+pub fn part1(input: &Grid<i32>) -> usize {
+    let mut results = HashSet::new();
+    for quarter_turns in 0..4 {
+        results.extend(visible_from_left(&input.rotated(quarter_turns)));
+    }
+    results.len()
+}
+
+#[aoc(day8, part2)]
+pub fn part2(input: &Grid<i32>) -> usize {
+    let mut scores = Grid::new(input.height(), input.width(), 1);
+    for quarter_turns in 0..4 {
+        let mut distances = Grid::new(input.height(), input.width(), 0);
+        viewing_distance_to_left(&input.rotated(quarter_turns), &mut distances);
+        for (pos, distance) in distances.iter() {
+            scores[pos] *= distance;
+        }
+    }
+    scores.iter().map(|(_, &score)| score).max().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "30373
+25512
+65332
+33549
+35390";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 21);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 8);
+    }
+}
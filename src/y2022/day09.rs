@@ -0,0 +1,123 @@
+use std::{collections::HashSet, str::FromStr};
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    parsing::{self, number, split_once},
+    point::Point2,
+};
+
+#[derive(Clone, Copy)]
+pub enum Dir {
+    U,
+    R,
+    D,
+    L,
+}
+
+impl Dir {
+    fn delta(self) -> Point2<i32> {
+        use Dir::*;
+        match self {
+            U => Point2::UP,
+            R => Point2::RIGHT,
+            D => Point2::DOWN,
+            L => Point2::LEFT,
+        }
+    }
+}
+
+/// Where `tail` ends up once `head` has moved: it steps towards `head` only
+/// when they are no longer touching.
+fn follow(head: Point2<i32>, tail: Point2<i32>) -> Point2<i32> {
+    if head.chebyshev(tail) > 1 {
+        tail + (head - tail).signum()
+    } else {
+        tail
+    }
+}
+
+impl FromStr for Dir {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Dir::*;
+        match s {
+            "R" => Ok(R),
+            "U" => Ok(U),
+            "D" => Ok(D),
+            "L" => Ok(L),
+            _ => Err(format!("couldn't parse dir from {s}")),
+        }
+    }
+}
+
+#[aoc_generator(day9)]
+pub fn parse(input: &str) -> Result<Vec<(Dir, usize)>> {
+    parsing::parse_lines(9, input, |line| {
+        let (dir, num) = split_once(line, " ")?;
+        let dir: Dir = dir.parse().map_err(|e: String| anyhow!(e))?;
+        let num = number(num)?;
+        Ok((dir, num))
+    })
+}
+
+#[aoc(day9, part1)]
+pub fn part1(input: &[(Dir, usize)]) -> usize {
+    simulate(input, 2)
+}
+
+#[aoc(day9, part2)]
+pub fn part2(input: &[(Dir, usize)]) -> usize {
+    simulate(input, 10)
+}
+
+/// Drags a rope of `len` knots through the moves, counting the squares the tail visits.
+fn simulate(input: &[(Dir, usize)], len: usize) -> usize {
+    let mut positions: HashSet<Point2<i32>> = HashSet::new();
+    let mut knots = vec![Point2::ORIGIN; len];
+    positions.insert(knots[len - 1]);
+    for (dir, dist) in input {
+        for _ in 0..*dist {
+            knots[0] += dir.delta();
+            for i in 1..knots.len() {
+                knots[i] = follow(knots[i - 1], knots[i]);
+            }
+            positions.insert(knots[len - 1]);
+        }
+    }
+    positions.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+
+    const LARGER_EXAMPLE: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 1);
+        assert_eq!(part2(&parse(LARGER_EXAMPLE).unwrap()), 36);
+    }
+}
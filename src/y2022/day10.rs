@@ -0,0 +1,248 @@
+use anyhow::Result;
+
+use crate::parsing::{self, number, strip_prefix};
+
+#[derive(Clone, Copy)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+impl Instruction {
+    fn deltas(&self) -> Vec<i32> {
+        match self {
+            Instruction::Noop => vec![0],
+            Instruction::Addx(n) => vec![0, *n],
+        }
+    }
+}
+
+#[aoc_generator(day10)]
+pub fn parse(input: &str) -> Result<Vec<Instruction>> {
+    parsing::parse_lines(10, input, |line| match line {
+        "noop" => Ok(Instruction::Noop),
+        line => {
+            let num = number(strip_prefix(line, "addx ")?)?;
+            Ok(Instruction::Addx(num))
+        }
+    })
+}
+
+#[aoc(day10, part1)]
+pub fn part1(input: &[Instruction]) -> i32 {
+    let signal_strengths = input
+        .iter()
+        .flat_map(Instruction::deltas)
+        .scan(1, |register, delta| {
+            let value = *register;
+            *register += delta;
+            Some(value)
+        })
+        .enumerate()
+        .map(|(i, register)| (i + 1, register))
+        .map(|(cycle_num, register)| i32::try_from(cycle_num).unwrap() * register)
+        .collect::<Vec<_>>();
+    [20, 60, 100, 140, 180, 220]
+        .into_iter()
+        .map(|cycle_num| signal_strengths[cycle_num - 1])
+        .sum()
+}
+
+/// Draws the CRT, one line per row. The screen starts with a newline so that it
+/// lines up when printed after the runner's label.
+#[aoc(day10, part2)]
+pub fn part2(input: &[Instruction]) -> String {
+    let mut screen = String::new();
+    for (index, position) in input
+        .iter()
+        .flat_map(Instruction::deltas)
+        .scan(1, |register, delta| {
+            let value = *register;
+            *register += delta;
+            Some(value)
+        })
+        .enumerate()
+    {
+        let horizontal_cursor = index % 40;
+
+        if horizontal_cursor == 0 {
+            screen.push('\n');
+        }
+        if (i32::try_from(horizontal_cursor).unwrap() - position).abs() <= 1 {
+            screen.push('#');
+        } else {
+            screen.push('.');
+        }
+    }
+    screen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop";
+
+    const EXAMPLE_SCREEN: &str = "
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 13140);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), EXAMPLE_SCREEN);
+    }
+}
@@ -0,0 +1,223 @@
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+use tracing::trace;
+
+use crate::parsing::{self, number, split_once, strip_prefix, Line};
+
+#[derive(Clone, Copy, Debug)]
+pub enum Operation {
+    Add(Option<i64>),
+    Mul(Option<i64>),
+}
+
+impl Operation {
+    /// Applies the operation to `old`; a `None` operand stands for `old` itself.
+    pub fn apply(self, old: i64) -> i64 {
+        match self {
+            Operation::Add(operand) => old + operand.unwrap_or(old),
+            Operation::Mul(operand) => old * operand.unwrap_or(old),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Monkey {
+    pub items: Vec<i64>,
+    pub operation: Operation,
+    pub test_divisor: i64,
+    pub iftrue: usize,
+    pub iffalse: usize,
+    pub inspection_count: i64,
+}
+
+fn parse_monkey<'a>(mut lines: impl Iterator<Item = Line<'a>>, header: Line<'a>) -> Result<Monkey> {
+    let mut next_line = || {
+        lines
+            .next()
+            .ok_or_else(|| header.error(anyhow!("monkey description is cut short")))
+    };
+
+    let items = next_line()?.parse(|line| {
+        let (_, items) = split_once(line, ": ")?;
+        items.split(", ").map(number).collect::<Result<Vec<i64>>>()
+    })?;
+
+    let operation = next_line()?.parse(|line| {
+        let (_, operation) = split_once(line, "new = old ")?;
+        let (operation, operand) = split_once(operation, " ")?;
+        let operand = (operand != "old").then(|| number(operand)).transpose()?;
+
+        match operation {
+            "+" => Ok(Operation::Add(operand)),
+            "*" => Ok(Operation::Mul(operand)),
+            s => bail!("unrecognized operation {s}"),
+        }
+    })?;
+
+    let test_divisor = next_line()?.parse(|line| {
+        let (_, test_divisor) = split_once(line, "divisible by ")?;
+        let test_divisor = number::<i64>(test_divisor)?;
+        if test_divisor <= 0 {
+            bail!("the test divisor must be positive");
+        }
+        Ok(test_divisor)
+    })?;
+
+    let iftrue = next_line()?.parse(|line| {
+        let (_, iftrue) = split_once(line, "true: throw to monkey ")?;
+        number::<usize>(iftrue)
+    })?;
+
+    let iffalse = next_line()?.parse(|line| {
+        let (_, iffalse) = split_once(line, "false: throw to monkey ")?;
+        number::<usize>(iffalse)
+    })?;
+
+    trace!(
+        ?items,
+        ?operation,
+        test_divisor,
+        iftrue,
+        iffalse,
+        "parsed monkey"
+    );
+    Ok(Monkey {
+        items,
+        operation,
+        test_divisor,
+        iftrue,
+        iffalse,
+        inspection_count: 0,
+    })
+}
+
+#[aoc_generator(day11)]
+pub fn parse(input: &str) -> Result<Vec<Monkey>> {
+    let monkeys = parsing::lines(11, input)
+        .chunks(7)
+        .into_iter()
+        .map(|mut chunk| {
+            let header = chunk.next().unwrap();
+            header.parse(|line| strip_prefix(line, "Monkey "))?;
+            parse_monkey(chunk, header)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    for monkey in &monkeys {
+        if monkey.iftrue >= monkeys.len() || monkey.iffalse >= monkeys.len() {
+            bail!("day 11: monkey throws to a monkey that doesn't exist");
+        }
+    }
+    Ok(monkeys)
+}
+
+#[aoc(day11, part1)]
+pub fn part1(input: &[Monkey]) -> i64 {
+    let mut monkeys = input.to_vec();
+
+    for _ in 1..=20 {
+        for i in 0..monkeys.len() {
+            let items = std::mem::take(&mut monkeys[i].items);
+            for worry_level in items {
+                monkeys[i].inspection_count += 1;
+                let worry_level = monkeys[i].operation.apply(worry_level);
+                let worry_level = worry_level / 3;
+                let test_result = worry_level % monkeys[i].test_divisor == 0;
+                let next_monkey_index = if test_result {
+                    monkeys[i].iftrue
+                } else {
+                    monkeys[i].iffalse
+                };
+                monkeys[next_monkey_index].items.push(worry_level);
+            }
+        }
+    }
+
+    monkeys
+        .into_iter()
+        .map(|monkey| -monkey.inspection_count)
+        .k_smallest(2)
+        .product()
+}
+
+#[aoc(day11, part2)]
+pub fn part2(input: &[Monkey]) -> i64 {
+    let mut monkeys = input.to_vec();
+    let test_divisor_product: i64 = monkeys.iter().map(|monkey| monkey.test_divisor).product();
+
+    for _ in 1..=10000 {
+        for i in 0..monkeys.len() {
+            let items = std::mem::take(&mut monkeys[i].items);
+            for worry_level in items {
+                monkeys[i].inspection_count += 1;
+                let worry_level = monkeys[i].operation.apply(worry_level) % test_divisor_product;
+                let test_result = worry_level % monkeys[i].test_divisor == 0;
+                let next_monkey_index = if test_result {
+                    monkeys[i].iftrue
+                } else {
+                    monkeys[i].iffalse
+                };
+                monkeys[next_monkey_index].items.push(worry_level);
+            }
+        }
+    }
+
+    monkeys
+        .into_iter()
+        .map(|monkey| -monkey.inspection_count)
+        .k_smallest(2)
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 10605);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 2713310158);
+    }
+
+    #[test]
+    fn zero_divisor_is_a_parse_error() {
+        let err = parse(&EXAMPLE.replace("divisible by 23", "divisible by 0")).unwrap_err();
+        assert!(err.to_string().starts_with("day 11, line 4"), "{err}");
+        assert!(
+            err.to_string()
+                .ends_with("the test divisor must be positive"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use anyhow::{bail, Result};
+use petgraph::{algo::dijkstra, graph::NodeIndex, Directed, Graph};
+
+use crate::grid::Grid;
+
+#[derive(Clone, Copy)]
+pub enum Spot {
+    Start,
+    End,
+    Other(i32),
+}
+
+impl Spot {
+    pub fn elevation(&self) -> i32 {
+        match self {
+            Spot::Start => 0,
+            Spot::End => ('z' as i32) - ('a' as i32),
+            Spot::Other(n) => *n,
+        }
+    }
+
+    pub fn can_go_to(&self, other: Spot) -> bool {
+        self.elevation() + 1 >= other.elevation()
+    }
+}
+
+#[aoc_generator(day12)]
+pub fn parse(input: &str) -> Result<Grid<Spot>> {
+    Grid::parse(12, input, |c| match c {
+        'S' => Ok(Spot::Start),
+        'E' => Ok(Spot::End),
+        'a'..='z' => Ok(Spot::Other(c as i32 - 'a' as i32)),
+        c => bail!("unexpected elevation {c:?}"),
+    })
+}
+
+/// One node per square, with an edge wherever a step is allowed.
+fn build_graph(input: &Grid<Spot>) -> (Graph<Spot, (), Directed>, Grid<NodeIndex>) {
+    let mut graph: Graph<Spot, (), Directed> = Graph::new();
+    let nodes = input.map(|&spot| graph.add_node(spot));
+    for (pos, spot) in input.iter() {
+        for neighbor in input.neighbors4(pos) {
+            if spot.can_go_to(input[neighbor]) {
+                graph.add_edge(nodes[pos], nodes[neighbor], ());
+            }
+        }
+    }
+    (graph, nodes)
+}
+
+fn find(input: &Grid<Spot>, pred: impl Fn(Spot) -> bool) -> (usize, usize) {
+    input
+        .iter()
+        .find_map(|(pos, &spot)| pred(spot).then_some(pos))
+        .unwrap()
+}
+
+#[aoc(day12, part1)]
+pub fn part1(input: &Grid<Spot>) -> i32 {
+    let (graph, nodes) = build_graph(input);
+    let start_loc = find(input, |spot| matches!(spot, Spot::Start));
+    let end_loc = find(input, |spot| matches!(spot, Spot::End));
+    let dijkstra_outcome = dijkstra(&graph, nodes[start_loc], Some(nodes[end_loc]), |_| 1i32);
+    dijkstra_outcome[&nodes[end_loc]]
+}
+
+#[aoc(day12, part2)]
+pub fn part2(input: &Grid<Spot>) -> i32 {
+    let (mut graph, nodes) = build_graph(input);
+    let end_loc = find(input, |spot| matches!(spot, Spot::End));
+    graph.reverse();
+    let graph = graph;
+    let dijkstra_outcome = dijkstra(&graph, nodes[end_loc], None, |_| 1i32);
+    input
+        .iter()
+        .filter_map(|(pos, spot)| match spot {
+            Spot::Other(0) => dijkstra_outcome.get(&nodes[pos]).copied(),
+            _ => None,
+        })
+        .min()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 31);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 29);
+    }
+}
@@ -1,9 +1,12 @@
+use anyhow::{anyhow, Result};
 use itertools::Itertools;
 use nom::{
     branch::alt, bytes::complete::tag, character::complete::digit1, combinator::map_res,
     multi::separated_list0, IResult, Parser,
 };
 
+use crate::parsing;
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum Packet {
     Num(i32),
@@ -12,43 +15,39 @@ pub enum Packet {
 
 impl PartialOrd for Packet {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         use std::cmp::Ordering;
         match (self, other) {
-            (Packet::Num(a), Packet::Num(b)) => a.partial_cmp(b),
-            (a @ Packet::Num(_), b @ Packet::List(_)) => {
-                Packet::List(vec![a.clone()]).partial_cmp(b)
-            }
-            (a @ Packet::List(_), b @ Packet::Num(_)) => {
-                a.partial_cmp(&Packet::List(vec![b.clone()]))
-            }
+            (Packet::Num(a), Packet::Num(b)) => a.cmp(b),
+            (a @ Packet::Num(_), b @ Packet::List(_)) => Packet::List(vec![a.clone()]).cmp(b),
+            (a @ Packet::List(_), b @ Packet::Num(_)) => a.cmp(&Packet::List(vec![b.clone()])),
             (Packet::List(a), Packet::List(b)) => {
                 for i in 0..b.len() {
                     if i >= a.len() {
-                        return Some(Ordering::Less);
+                        return Ordering::Less;
                     }
 
-                    let item_cmp = a[i].partial_cmp(&b[i]).unwrap();
+                    let item_cmp = a[i].cmp(&b[i]);
                     if item_cmp != Ordering::Equal {
-                        return Some(item_cmp);
+                        return item_cmp;
                     }
                 }
                 if a.len() == b.len() {
-                    Some(Ordering::Equal)
+                    Ordering::Equal
                 } else {
-                    Some(Ordering::Greater)
+                    Ordering::Greater
                 }
             }
         }
     }
 }
 
-impl Ord for Packet {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
-fn packet<'a>(input: &'a str) -> IResult<&'a str, Packet> {
+fn packet(input: &str) -> IResult<&str, Packet> {
     alt((
         map_res(digit1, |s: &str| s.parse::<i32>().map(Packet::Num)),
         list.map(Packet::List),
@@ -62,18 +61,25 @@ fn list<'a>(input: &'a str) -> IResult<&'a str, Vec<Packet>> {
     Ok((input, packets))
 }
 
+fn parse_packet(line: &str) -> Result<Packet> {
+    match packet(line) {
+        Ok(("", packet)) => Ok(packet),
+        Ok((rest, _)) => Err(anyhow!("unexpected trailing {rest:?}")),
+        Err(e) => Err(anyhow!("invalid packet: {e}")),
+    }
+}
+
 #[aoc_generator(day13)]
-pub fn parse(input: &str) -> Vec<(Packet, Packet)> {
-    input
-        .lines()
+pub fn parse(input: &str) -> Result<Vec<(Packet, Packet)>> {
+    parsing::lines(13, input)
         .chunks(3)
         .into_iter()
-        .map(|chunk| {
-            let mut packets = chunk.take(2).map(|line| {
-                let (_, packet) = packet(line).unwrap();
-                packet
-            });
-            (packets.next().unwrap(), packets.next().unwrap())
+        .map(|mut chunk| {
+            let first = chunk.next().unwrap();
+            let second = chunk
+                .next()
+                .ok_or_else(|| first.error(anyhow!("packet has no partner")))?;
+            Ok((first.parse(parse_packet)?, second.parse(parse_packet)?))
         })
         .collect()
 }
@@ -110,3 +116,42 @@ pub fn part2(input: &[(Packet, Packet)]) -> usize {
         .map(|(i, _)| i + 1)
         .product()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 140);
+    }
+}
@@ -0,0 +1,153 @@
+use anyhow::Result;
+use itertools::Itertools;
+
+use crate::{
+    grid::SparseGrid,
+    parsing::{self, number, split_once},
+};
+
+#[aoc_generator(day14)]
+pub fn parse(input: &str) -> Result<Vec<Vec<(i32, i32)>>> {
+    parsing::parse_lines(14, input, |line| {
+        line.split(" -> ")
+            .map(|pair| {
+                let (a, b) = split_once(pair, ",")?;
+                Ok((number(a)?, number(b)?))
+            })
+            .collect()
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Tile {
+    Rock,
+    Sand,
+}
+
+/// The rock paths drawn into a grid, plus the `y` of the lowest rock.
+fn build_cave(input: &[Vec<(i32, i32)>]) -> (SparseGrid<Tile>, i64) {
+    let mut grid = SparseGrid::new();
+    let mut bottom = 0;
+    for path in input {
+        for (a, b) in path.iter().tuple_windows() {
+            for x in a.0.min(b.0)..=a.0.max(b.0) {
+                for y in a.1.min(b.1)..=a.1.max(b.1) {
+                    grid.insert((i64::from(x), i64::from(y)), Tile::Rock);
+                    bottom = bottom.max(i64::from(y));
+                }
+            }
+        }
+    }
+    (grid, bottom)
+}
+
+pub fn render(grid: &SparseGrid<Tile>) -> String {
+    grid.render(|tile| match tile {
+        Some(Tile::Rock) => '#',
+        Some(Tile::Sand) => 'o',
+        None => '.',
+    })
+}
+
+#[aoc(day14, part1)]
+pub fn part1(input: &[Vec<(i32, i32)>]) -> i32 {
+    let (mut grid, bottom) = build_cave(input);
+    let mut count = 0;
+    loop {
+        let mut sand_loc = (500, 0);
+        let exited = loop {
+            if sand_loc.1 > bottom {
+                break true;
+            }
+
+            if !grid.contains((sand_loc.0, sand_loc.1 + 1)) {
+                sand_loc.1 += 1;
+                continue;
+            }
+
+            if !grid.contains((sand_loc.0 - 1, sand_loc.1 + 1)) {
+                sand_loc.0 -= 1;
+                sand_loc.1 += 1;
+                continue;
+            }
+
+            if !grid.contains((sand_loc.0 + 1, sand_loc.1 + 1)) {
+                sand_loc.0 += 1;
+                sand_loc.1 += 1;
+                continue;
+            }
+
+            break false;
+        };
+
+        if exited {
+            break;
+        } else {
+            count += 1;
+            grid.insert(sand_loc, Tile::Sand);
+        }
+    }
+
+    count
+}
+
+#[aoc(day14, part2)]
+pub fn part2(input: &[Vec<(i32, i32)>]) -> i32 {
+    let (mut grid, bottom) = build_cave(input);
+    let bottom = bottom + 2;
+    let mut count = 0;
+    loop {
+        let mut sand_loc = (500, 0);
+        loop {
+            if sand_loc.1 == bottom - 1 {
+                break;
+            }
+
+            if !grid.contains((sand_loc.0, sand_loc.1 + 1)) {
+                sand_loc.1 += 1;
+                continue;
+            }
+
+            if !grid.contains((sand_loc.0 - 1, sand_loc.1 + 1)) {
+                sand_loc.0 -= 1;
+                sand_loc.1 += 1;
+                continue;
+            }
+
+            if !grid.contains((sand_loc.0 + 1, sand_loc.1 + 1)) {
+                sand_loc.0 += 1;
+                sand_loc.1 += 1;
+                continue;
+            }
+
+            break;
+        }
+
+        count += 1;
+        grid.insert(sand_loc, Tile::Sand);
+
+        if grid.contains((500, 0)) {
+            break;
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 24);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 93);
+    }
+}
@@ -0,0 +1,110 @@
+use std::{collections::BTreeSet, ops::RangeInclusive};
+
+use anyhow::{bail, Result};
+
+use crate::{
+    interval::IntervalSet,
+    parsing::{self, number, split_once, strip_prefix},
+    point::Point2,
+};
+
+/// A sensor position and the position of its closest beacon.
+pub type Reading = (Point2<i64>, Point2<i64>);
+
+#[aoc_generator(day15)]
+pub fn parse(input: &str) -> Result<Vec<Reading>> {
+    fn point(s: &str) -> Result<Point2<i64>> {
+        let s = strip_prefix(s, "x=")?;
+        let (x, y) = split_once(s, ", y=")?;
+        Ok(Point2::new(number(x)?, number(y)?))
+    }
+    parsing::parse_lines(15, input, |line| {
+        let line = strip_prefix(line, "Sensor at ")?;
+        let (sensor, beacon) = split_once(line, ": closest beacon is at ")?;
+        Ok((point(sensor)?, point(beacon)?))
+    })
+}
+
+const Y: i64 = 2000000;
+
+#[aoc(day15, part1)]
+pub fn part1(input: &[Reading]) -> usize {
+    part1_impl(input, Y)
+}
+
+/// The span of row `y` that `sensor` can see, out to the distance of its beacon.
+fn coverage((sensor, beacon): Reading, y: i64) -> Option<RangeInclusive<i64>> {
+    let margin = sensor.manhattan(beacon) - (sensor.y - y).abs();
+    (margin >= 0).then(|| sensor.x - margin..=sensor.x + margin)
+}
+
+pub fn part1_impl(input: &[Reading], y: i64) -> usize {
+    let covered: IntervalSet<i64> = input
+        .iter()
+        .filter_map(|&reading| coverage(reading, y))
+        .collect();
+    let beacons: BTreeSet<i64> = input
+        .iter()
+        .filter(|(_, beacon)| beacon.y == y && covered.contains(beacon.x))
+        .map(|(_, beacon)| beacon.x)
+        .collect();
+    covered.len().expect("i64 ranges fit in a u128") as usize - beacons.len()
+}
+
+#[aoc(day15, part2)]
+pub fn part2(input: &[Reading]) -> Result<i64> {
+    part2_impl(input, 4000000, 4000000)
+}
+
+/// Finds the one position in the search area that no sensor can see, by
+/// cutting each sensor's coverage out of the area row by row.
+pub fn part2_impl(input: &[Reading], limit_x: i64, limit_y: i64) -> Result<i64> {
+    let mut free = IntervalSet::new();
+    for y in 0..=limit_y {
+        free.clear();
+        free.insert(0..=limit_x);
+        for &reading in input {
+            if let Some(span) = coverage(reading, y) {
+                free.remove(span);
+            }
+        }
+        if let Some(x) = free.bounds() {
+            return Ok(x.start() * 4000000 + y);
+        }
+    }
+    bail!("day 15: every position in the search area is in range of a sensor")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1_impl(&parse(EXAMPLE).unwrap(), 10), 26);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            part2_impl(&parse(EXAMPLE).unwrap(), 20, 20).unwrap(),
+            56000011
+        );
+        assert!(part2_impl(&parse(EXAMPLE).unwrap(), 10, 10).is_err());
+    }
+}
@@ -1,35 +1,37 @@
 use std::collections::{BTreeSet, HashMap};
 
+use anyhow::Result;
 use itertools::Itertools;
 use petgraph::{
     algo::{bellman_ford, floyd_warshall},
     stable_graph::{DefaultIx, NodeIndex},
     Directed, Graph,
 };
+use tracing::{debug, trace};
+
+use crate::parsing::{self, number, split_once, strip_prefix};
 
 #[aoc_generator(day16)]
-pub fn parse(input: &str) -> Vec<(String, i32, Vec<String>)> {
-    input
-        .lines()
-        .map(|line| {
-            let (valve_desc, tunnels) = line.split_once("; ").unwrap();
-            let (valve, flow_rate) = valve_desc.split_once(" has flow rate=").unwrap();
-            let valve = valve.strip_prefix("Valve ").unwrap().to_owned();
-            let flow_rate = flow_rate.parse::<i32>().unwrap();
-            let tunnels = tunnels
-                .strip_prefix("tunnels lead to valves ")
-                .unwrap_or_else(|| tunnels.strip_prefix("tunnel leads to valve ").unwrap());
-            let tunnels = tunnels
-                .split(", ")
-                .map(|s| s.to_owned())
-                .collect::<Vec<_>>();
-            (valve, flow_rate, tunnels)
-        })
-        .collect()
+pub fn parse(input: &str) -> Result<Vec<(String, i32, Vec<String>)>> {
+    parsing::parse_lines(16, input, |line| {
+        let (valve_desc, tunnels) = split_once(line, "; ")?;
+        let (valve, flow_rate) = split_once(valve_desc, " has flow rate=")?;
+        let valve = strip_prefix(valve, "Valve ")?.to_owned();
+        let flow_rate = number(flow_rate)?;
+        let tunnels = match tunnels.strip_prefix("tunnels lead to valves ") {
+            Some(tunnels) => tunnels,
+            None => strip_prefix(tunnels, "tunnel leads to valve ")?,
+        };
+        let tunnels = tunnels
+            .split(", ")
+            .map(|s| s.to_owned())
+            .collect::<Vec<_>>();
+        Ok((valve, flow_rate, tunnels))
+    })
 }
 
 #[aoc(day16, part1)]
-pub fn part1(input: &[(String, i32, Vec<String>)]) -> f64 {
+pub fn part1(input: &[(String, i32, Vec<String>)]) -> i32 {
     let mut graph: Graph<String, i32, Directed> = Graph::new();
     let mut name_to_node: HashMap<String, NodeIndex<DefaultIx>> = HashMap::new();
     let mut name_to_flow_rate: HashMap<String, i32> = HashMap::new();
@@ -106,13 +108,13 @@ pub fn part1(input: &[(String, i32, Vec<String>)]) -> f64 {
     let end_index = time_graph.add_node(("END".to_owned(), 30, BTreeSet::new()));
 
     loop {
-        println!("Graph has {} nodes", time_graph.node_count());
+        trace!(nodes = time_graph.node_count(), "growing time graph");
         if nodes_just_added.is_empty() {
             // We're done building the graph.
             break;
         }
 
-        for node in std::mem::replace(&mut nodes_just_added, Vec::new()).into_iter() {
+        for node in std::mem::take(&mut nodes_just_added).into_iter() {
             let node_index = name_time_to_node_index[&node];
             let (name, time, visited) = &node;
             let visited = {
@@ -120,6 +122,8 @@ pub fn part1(input: &[(String, i32, Vec<String>)]) -> f64 {
                 visited.insert(name.clone());
                 visited
             };
+            // Stopping here is always an option, even once nothing else is reachable.
+            time_graph.add_edge(node_index, end_index, 0.0);
             for edge in graph.edges(name_to_node[name]) {
                 let (dest, dist) = edge.weight();
                 if visited.contains(dest) {
@@ -140,13 +144,13 @@ pub fn part1(input: &[(String, i32, Vec<String>)]) -> f64 {
                     dest_index,
                     f64::from(-(30 - next_time) * name_to_flow_rate[dest]),
                 );
-                time_graph.add_edge(node_index, end_index, 0.0);
             }
         }
     }
 
+    // Edge weights are negated pressure, so the shortest path is the best plan.
     let paths = bellman_ford(&time_graph, start_index).unwrap();
-    paths.distances[end_index.index()]
+    -paths.distances[end_index.index()] as i32
     // bellman_ford(time_graph, source)
 
     // Now that we have this complete graph with extra edges representing
@@ -175,25 +179,6 @@ pub fn part1(input: &[(String, i32, Vec<String>)]) -> f64 {
 
 #[aoc(day16, part2)]
 pub fn part2(input: &[(String, i32, Vec<String>)]) -> i32 {
-    const DO_EXAMPLE: bool = false;
-    if DO_EXAMPLE {
-        return part2_impl(&parse(
-            "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
-Valve BB has flow rate=13; tunnels lead to valves CC, AA
-Valve CC has flow rate=2; tunnels lead to valves DD, BB
-Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
-Valve EE has flow rate=3; tunnels lead to valves FF, DD
-Valve FF has flow rate=0; tunnels lead to valves EE, GG
-Valve GG has flow rate=0; tunnels lead to valves FF, HH
-Valve HH has flow rate=22; tunnel leads to valve GG
-Valve II has flow rate=0; tunnels lead to valves AA, JJ
-Valve JJ has flow rate=21; tunnel leads to valve II",
-        ));
-    }
-    part2_impl(input)
-}
-
-pub fn part2_impl(input: &[(String, i32, Vec<String>)]) -> i32 {
     let mut graph: Graph<String, i32, Directed> = Graph::new();
     let mut name_to_node: HashMap<String, NodeIndex<DefaultIx>> = HashMap::new();
     let mut name_to_flow_rate: HashMap<String, i32> = HashMap::new();
@@ -257,7 +242,7 @@ pub fn part2_impl(input: &[(String, i32, Vec<String>)]) -> i32 {
     let (score, a, b) = nodes_to_score
         .clone()
         .into_iter()
-        .cartesian_product(nodes_to_score.into_iter())
+        .cartesian_product(nodes_to_score)
         .filter_map(|((a_set, a_score), (b_set, b_score))| {
             if !a_set.is_disjoint(&b_set) {
                 return None;
@@ -266,7 +251,7 @@ pub fn part2_impl(input: &[(String, i32, Vec<String>)]) -> i32 {
         })
         .max()
         .unwrap();
-    println!("{a:?}\n{b:?}");
+    debug!(score, you = ?a, elephant = ?b, "chose valve sets");
     score
 }
 
@@ -328,3 +313,29 @@ impl Solver {
         best
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 1651);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 1707);
+    }
+}
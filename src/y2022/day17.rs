@@ -1,6 +1,10 @@
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{HashMap, HashSet};
 
+use anyhow::{bail, Result};
 use itertools::Itertools;
+use tracing::debug;
+
+use crate::{grid::SparseGrid, parsing, point::Point2};
 
 #[derive(Clone, Copy)]
 pub enum Jet {
@@ -8,68 +12,27 @@ pub enum Jet {
     Right,
 }
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Debug)]
-pub struct Point {
-    pub x: i64,
-    pub y: i64,
-}
-
-impl Point {
-    pub fn add(
-        self,
-        Point {
-            x: other_x,
-            y: other_y,
-        }: Point,
-    ) -> Point {
-        let Point { x, y } = self;
-        Point {
-            x: x + other_x,
-            y: y + other_y,
-        }
-    }
-}
-
-impl Default for Point {
-    fn default() -> Self {
-        Point { x: 0, y: 0 }
-    }
-}
-
-impl From<(i64, i64)> for Point {
-    fn from((x, y): (i64, i64)) -> Self {
-        Point { x, y }
-    }
-}
-
 #[derive(Clone, Debug)]
 pub struct Rock {
-    pub offset: Point,
-    pub body_pattern: HashSet<Point>,
+    pub offset: Point2<i64>,
+    pub body_pattern: HashSet<Point2<i64>>,
 }
 
 impl Rock {
-    pub fn body(&self) -> HashSet<Point> {
+    pub fn body(&self) -> HashSet<Point2<i64>> {
         self.body_pattern
             .iter()
-            .map(|point| (*point).add(self.offset))
+            .map(|&point| point + self.offset)
             .collect()
     }
 
     pub fn pretty(&self) {
-        let points = self.body_pattern.clone();
-        let max_y = points.iter().map(|point| point.y).max().unwrap();
-        let max_x = points.iter().map(|point| point.x).max().unwrap();
-        for y in (0..=max_y).rev() {
-            for x in 0..=max_x {
-                if points.contains(&((x, y).into())) {
-                    print!("#")
-                } else {
-                    print!(".")
-                }
-            }
-            println!()
-        }
+        let shape = self
+            .body_pattern
+            .iter()
+            .map(|&point| (point.into(), ()))
+            .collect::<SparseGrid<()>>();
+        debug!("rock shape:\n{}", render_upwards(&shape));
     }
 
     pub fn displaced(self, jet: Jet) -> Rock {
@@ -80,11 +43,11 @@ impl Rock {
         let next = match jet {
             Jet::Left => Rock {
                 body_pattern,
-                offset: offset.add(Point { x: -1, y: 0 }),
+                offset: offset + Point2::LEFT,
             },
             Jet::Right => Rock {
                 body_pattern,
-                offset: offset.add(Point { x: 1, y: 0 }),
+                offset: offset + Point2::RIGHT,
             },
         };
         if next
@@ -105,30 +68,34 @@ impl Rock {
         } = self;
         Rock {
             body_pattern,
-            offset: offset.add(Point { x: 0, y: -1 }),
+            offset: offset + Point2::DOWN,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Tower {
-    pub occupied: HashSet<Point>,
+    pub occupied: SparseGrid<()>,
     pub rockmod: i64,
     pub height: i64,
+    /// The height of the highest rock in each column.
+    pub column_heights: [i64; 7],
 }
 
 impl Tower {
     pub fn new() -> Tower {
-        Tower {
-            occupied: HashSet::new(),
-            rockmod: 0,
-            height: 0,
-        }
+        Tower::default()
+    }
+
+    fn is_clear(&self, rock: &Rock) -> bool {
+        rock.body()
+            .into_iter()
+            .all(|point| !self.occupied.contains(point.into()))
     }
 
     pub fn jet(&self, rock: Rock, jet: Jet) -> Rock {
         let next = rock.clone().displaced(jet);
-        if next.body().is_disjoint(&self.occupied) {
+        if self.is_clear(&next) {
             next
         } else {
             rock
@@ -137,9 +104,7 @@ impl Tower {
 
     pub fn drop(&self, rock: Rock) -> Result<Rock, Rock> {
         let next = rock.clone().dropped();
-        if !next.body().is_disjoint(&self.occupied)
-            || next.body().into_iter().any(|point| point.y < 0)
-        {
+        if !self.is_clear(&next) || next.body().into_iter().any(|point| point.y < 0) {
             Err(rock)
         } else {
             Ok(next)
@@ -147,12 +112,17 @@ impl Tower {
     }
 
     pub fn place(&mut self, rock: Rock) -> Option<i64> {
-        assert!(rock.body().is_disjoint(&self.occupied));
-        assert!(matches!(self.drop(rock.clone()), Err(_)));
-        self.occupied.extend(rock.body());
+        assert!(self.is_clear(&rock));
+        assert!(self.drop(rock.clone()).is_err());
+        self.occupied
+            .extend(rock.body().into_iter().map(|point| (point.into(), ())));
         self.height = self
             .height
             .max(rock.body().into_iter().map(|point| point.y).max().unwrap() + 1);
+        for point in rock.body() {
+            let column = &mut self.column_heights[point.x as usize];
+            *column = (*column).max(point.y + 1);
+        }
 
         let ys_to_check = rock
             .body()
@@ -160,7 +130,7 @@ impl Tower {
             .map(|point| point.y)
             .collect::<HashSet<_>>();
         for y in ys_to_check.into_iter().sorted_by_key(|y| -y) {
-            if (0i64..7i64).all(|x| self.occupied.contains(&(x, y).into())) {
+            if (0i64..7i64).all(|x| self.occupied.contains((x, y))) {
                 return Some(y);
             }
         }
@@ -183,43 +153,43 @@ impl Tower {
         self.rockmod = (self.rockmod + 1) % 5;
         match curr_rockmod {
             0 => Rock {
-                offset: Point::default(),
+                offset: Point2::ORIGIN,
                 body_pattern: HashSet::from_iter(
                     [(0, 0), (1, 0), (2, 0), (3, 0)]
                         .into_iter()
-                        .map(Point::from),
+                        .map(Point2::from),
                 ),
             },
             1 => Rock {
-                offset: Point::default(),
+                offset: Point2::ORIGIN,
                 body_pattern: HashSet::from_iter(
                     [(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)]
                         .into_iter()
-                        .map(Point::from),
+                        .map(Point2::from),
                 ),
             },
             2 => Rock {
-                offset: Point::default(),
+                offset: Point2::ORIGIN,
                 body_pattern: HashSet::from_iter(
                     [(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]
                         .into_iter()
-                        .map(Point::from),
+                        .map(Point2::from),
                 ),
             },
             3 => Rock {
-                offset: Point::default(),
+                offset: Point2::ORIGIN,
                 body_pattern: HashSet::from_iter(
                     [(0, 0), (0, 1), (0, 2), (0, 3)]
                         .into_iter()
-                        .map(Point::from),
+                        .map(Point2::from),
                 ),
             },
             4 => Rock {
-                offset: Point::default(),
+                offset: Point2::ORIGIN,
                 body_pattern: HashSet::from_iter(
                     [(0, 0), (0, 1), (1, 0), (1, 1)]
                         .into_iter()
-                        .map(Point::from),
+                        .map(Point2::from),
                 ),
             },
             n => panic!("{n}"),
@@ -227,37 +197,29 @@ impl Tower {
     }
 }
 
+/// Renders cells with `y` growing upwards, the way the chamber is drawn.
+fn render_upwards(grid: &SparseGrid<()>) -> String {
+    grid.render(|cell| if cell.is_some() { '#' } else { '.' })
+        .lines()
+        .rev()
+        .join("\n")
+}
+
 #[aoc_generator(day17)]
-pub fn parse(input: &str) -> Vec<Jet> {
-    input
-        .chars()
-        .map(|c| match c {
-            '>' => Jet::Right,
-            '<' => Jet::Left,
-            _ => panic!("{c}"),
-        })
-        .collect()
+pub fn parse(input: &str) -> Result<Vec<Jet>> {
+    parsing::single_line(17, input)?.parse(|line| {
+        line.chars()
+            .map(|c| match c {
+                '>' => Ok(Jet::Right),
+                '<' => Ok(Jet::Left),
+                _ => bail!("unexpected jet {c:?}"),
+            })
+            .collect()
+    })
 }
 
 #[aoc(day17, part1)]
 pub fn part1(input: &[Jet]) -> i64 {
-    // {
-    //     let mut tower = Tower::new();
-    //     for _ in 1..=5 {
-    //         tower.spawn().pretty();
-    //         println!()
-    //     }
-    // }
-
-    println!(
-        "{}",
-        part1_impl(&parse(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>"))
-    );
-    part1_impl(input)
-    // 0
-}
-
-pub fn part1_impl(input: &[Jet]) -> i64 {
     let mut num_rocks_placed = 0;
     let mut tower = Tower::new();
     let mut jet_i = 0;
@@ -282,16 +244,18 @@ pub fn part1_impl(input: &[Jet]) -> i64 {
     tower.height
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+/// What decides how the rest of the tower grows: the next rock and jet, and
+/// how far below the top each column's highest rock is.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct CacheKey {
-    pub footprint: BTreeSet<Point>,
+    pub surface: [i64; 7],
     pub jet_i: usize,
     pub rockmod: i64,
 }
 
 #[derive(Clone, Copy, Debug)]
 pub struct CacheVal {
-    pub full_y: i64,
+    pub height: i64,
     pub num_rocks_placed: i64,
 }
 
@@ -303,57 +267,63 @@ pub fn part2(input: &[Jet]) -> i64 {
     let mut tower = Tower::new();
     let mut jet_i = 0;
     let mut cache: HashMap<CacheKey, CacheVal> = HashMap::new();
+    let mut skipped = false;
     let mut artificial_height = 0i64;
     while num_rocks_placed < MAX_ROCKS {
         let mut rock = tower.spawn();
-        let newly_full_y = loop {
+        loop {
             rock = tower.jet(rock, input[jet_i]);
             jet_i = (jet_i + 1) % input.len();
             rock = match tower.drop(rock) {
                 Ok(rock) => rock,
                 Err(rock) => {
-                    let newly_full_y = tower.place(rock);
+                    tower.place(rock);
                     num_rocks_placed += 1;
-                    break newly_full_y;
+                    break;
                 }
             }
+        }
+        if skipped {
+            continue;
+        }
+        let cache_key = CacheKey {
+            surface: tower.column_heights.map(|column| tower.height - column),
+            jet_i,
+            rockmod: tower.rockmod,
         };
-        if let Some(newly_full_y) = newly_full_y {
-            let mut footprint: BTreeSet<Point> = BTreeSet::new();
-            let height = tower.height;
-            let rockmod = tower.rockmod;
-            for x in 0..7 {
-                for y in newly_full_y..height {
-                    if tower.occupied.contains(&(x, y).into()) {
-                        footprint.insert((x, y - height).into());
-                    }
-                }
-            }
-            let cache_key = CacheKey {
-                footprint,
-                jet_i,
-                rockmod,
-            };
-            if let Some(old_cache_val) = cache.get(&cache_key).cloned() {
-                println!("cache hit: {}", cache.len());
-                let CacheVal {
-                    full_y: old_full_y,
-                    num_rocks_placed: old_num_rocks_placed,
-                } = old_cache_val;
-                let full_y_delta = newly_full_y - old_full_y;
-                let rocks_placed_delta = num_rocks_placed - old_num_rocks_placed;
-                let num_cycles_to_do = (MAX_ROCKS - num_rocks_placed) / rocks_placed_delta;
-                artificial_height += num_cycles_to_do * full_y_delta;
-                num_rocks_placed += num_cycles_to_do * rocks_placed_delta;
-                cache.clear();
-            } else {
-                let new_cache_val = CacheVal {
-                    full_y: newly_full_y,
-                    num_rocks_placed: num_rocks_placed,
-                };
-                cache.insert(cache_key, new_cache_val);
-            }
+        let cache_val = CacheVal {
+            height: tower.height,
+            num_rocks_placed,
+        };
+        if let Some(old) = cache.insert(cache_key, cache_val) {
+            debug!(
+                cached = cache.len(),
+                num_rocks_placed, "found a repeating cycle"
+            );
+            let height_delta = tower.height - old.height;
+            let rocks_placed_delta = num_rocks_placed - old.num_rocks_placed;
+            let num_cycles_to_do = (MAX_ROCKS - num_rocks_placed) / rocks_placed_delta;
+            artificial_height += num_cycles_to_do * height_delta;
+            num_rocks_placed += num_cycles_to_do * rocks_placed_delta;
+            skipped = true;
         }
     }
     tower.height + artificial_height
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 3068);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 1514285714288);
+    }
+}
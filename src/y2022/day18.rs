@@ -0,0 +1,102 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use petgraph::unionfind::UnionFind;
+
+use crate::{
+    parsing::{self, number, split_once},
+    point::Point3,
+};
+
+#[aoc_generator(day18)]
+pub fn parse(input: &str) -> Result<Vec<Point3<i32>>> {
+    parsing::parse_lines(18, input, |line| {
+        let (x, line) = split_once(line, ",")?;
+        let (y, z) = split_once(line, ",")?;
+        Ok(Point3::new(number(x)?, number(y)?, number(z)?))
+    })
+}
+
+#[aoc(day18, part1)]
+pub fn part1(input: &[Point3<i32>]) -> usize {
+    let grid: HashSet<Point3<i32>> = input.iter().copied().collect();
+    grid.iter()
+        .map(|&point| {
+            Point3::DIRECTIONS
+                .into_iter()
+                .filter(|&delta| !grid.contains(&(point + delta)))
+                .count()
+        })
+        .sum()
+}
+
+fn unionize_point(Point3 { x, y, z }: Point3<i32>) -> usize {
+    usize::try_from(x + 10).unwrap()
+        + usize::try_from(y + 10).unwrap() * 100
+        + usize::try_from(z + 10).unwrap() * 10000
+}
+
+#[aoc(day18, part2)]
+pub fn part2(input: &[Point3<i32>]) -> usize {
+    let grid: HashSet<Point3<i32>> = input.iter().copied().collect();
+    let max_x = grid.iter().map(|point| point.x).max().unwrap();
+    let max_y = grid.iter().map(|point| point.y).max().unwrap();
+    let max_z = grid.iter().map(|point| point.z).max().unwrap();
+
+    let mut union_find =
+        UnionFind::new(unionize_point(Point3::new(max_x + 2, max_y + 2, max_z + 2)));
+    for x in (-1)..=(max_x + 1) {
+        for y in (-1)..=(max_y + 1) {
+            for z in (-1)..=(max_z + 1) {
+                let point = Point3::new(x, y, z);
+                if !grid.contains(&point) {
+                    for delta in Point3::DIRECTIONS {
+                        let neighbor = point + delta;
+                        if !grid.contains(&neighbor) {
+                            union_find.union(unionize_point(point), unionize_point(neighbor));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let outside = unionize_point(Point3::new(-1, -1, -1));
+    grid.iter()
+        .map(|&point| {
+            Point3::DIRECTIONS
+                .into_iter()
+                .filter(|&delta| union_find.equiv(outside, unionize_point(point + delta)))
+                .count()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 64);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 58);
+    }
+}
@@ -1,9 +1,13 @@
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::BTreeSet,
     ops::{Add, AddAssign, Mul, Neg},
 };
 
+use anyhow::Result;
 use itertools::Itertools;
+use tracing::debug;
+
+use crate::parsing::{self, number, split_once};
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Ore(i32);
@@ -104,36 +108,27 @@ pub struct Blueprint {
 }
 
 #[aoc_generator(day19)]
-pub fn parse(input: &str) -> Vec<Blueprint> {
-    input
-        .lines()
-        .map(|line| {
-            let line = line.trim();
-            let (_, line) = line.split_once("Each ore robot costs ").unwrap();
-            let (ore, line) = line.split_once(" ore. Each clay robot costs ").unwrap();
-            let (clay, line) = line.split_once(" ore. Each obsidian robot costs ").unwrap();
-            let (obsidian_ore, line) = line.split_once(" ore and ").unwrap();
-            let (obsidian_clay, line) = line.split_once(" clay. Each geode robot costs ").unwrap();
-            let (geode_ore, line) = line.split_once(" ore and ").unwrap();
-            let (geode_obsidian, _) = line.split_once(" obsidian.").unwrap();
-
-            Blueprint {
-                ore_robot_cost: Ore(ore.parse().unwrap()),
-                clay_robot_cost: Ore(clay.parse().unwrap()),
-                obsidian_robot_cost: (
-                    Ore(obsidian_ore.parse().unwrap()),
-                    Clay(obsidian_clay.parse().unwrap()),
-                ),
-                geode_robot_cost: (
-                    Ore(geode_ore.parse().unwrap()),
-                    Obsidian(geode_obsidian.parse().unwrap()),
-                ),
-            }
+pub fn parse(input: &str) -> Result<Vec<Blueprint>> {
+    parsing::parse_lines(19, input, |line| {
+        let line = line.trim();
+        let (_, line) = split_once(line, "Each ore robot costs ")?;
+        let (ore, line) = split_once(line, " ore. Each clay robot costs ")?;
+        let (clay, line) = split_once(line, " ore. Each obsidian robot costs ")?;
+        let (obsidian_ore, line) = split_once(line, " ore and ")?;
+        let (obsidian_clay, line) = split_once(line, " clay. Each geode robot costs ")?;
+        let (geode_ore, line) = split_once(line, " ore and ")?;
+        let (geode_obsidian, _) = split_once(line, " obsidian.")?;
+
+        Ok(Blueprint {
+            ore_robot_cost: Ore(number(ore)?),
+            clay_robot_cost: Ore(number(clay)?),
+            obsidian_robot_cost: (Ore(number(obsidian_ore)?), Clay(number(obsidian_clay)?)),
+            geode_robot_cost: (Ore(number(geode_ore)?), Obsidian(number(geode_obsidian)?)),
         })
-        .collect()
+    })
 }
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
 struct Robots {
     ore_robots: i32,
     clay_robots: i32,
@@ -141,17 +136,6 @@ struct Robots {
     geode_robots: i32,
 }
 
-impl Default for Robots {
-    fn default() -> Self {
-        Robots {
-            ore_robots: 0,
-            clay_robots: 0,
-            obsidian_robots: 0,
-            geode_robots: 0,
-        }
-    }
-}
-
 impl Add for Robots {
     type Output = Robots;
     fn add(self, rhs: Self) -> Self::Output {
@@ -289,33 +273,64 @@ struct State {
     not_allowed_next: BTreeSet<RobotType>,
 }
 
+/// A depth-first search for the most geodes, pruning any branch that can't
+/// beat the best plan found so far even if it built a geode robot every
+/// remaining minute.
 struct Solver19 {
     blueprint: Blueprint,
-    cache: HashMap<State, i32>,
+    best: i32,
+    visited: usize,
 }
 
 impl Solver19 {
-    fn solve(&mut self, state: State) -> i32 {
-        if !self.cache.contains_key(&state) {
-            let result = self.solve_impl(state.clone());
-            if state.minutes_left < 6 && state.minutes_left > 0 {
-                self.cache.insert(state, result);
-            }
-            return result;
+    fn new(blueprint: Blueprint) -> Solver19 {
+        Solver19 {
+            blueprint,
+            best: 0,
+            visited: 0,
         }
-        self.cache[&state]
-        // self.solve_impl(state)
     }
 
-    fn solve_impl(&mut self, state: State) -> i32 {
+    /// The most geodes that can be open after `minutes`, starting with one ore robot.
+    fn run(&mut self, minutes: i32) -> i32 {
+        self.solve(
+            State {
+                minutes_left: minutes,
+                resources: Resources {
+                    ore: Ore(0),
+                    clay: Clay(0),
+                    obsidian: Obsidian(0),
+                },
+                robots: Robots {
+                    ore_robots: 1,
+                    clay_robots: 0,
+                    obsidian_robots: 0,
+                    geode_robots: 0,
+                },
+                not_allowed_next: BTreeSet::new(),
+            },
+            0,
+        );
+        self.best
+    }
+
+    /// Searches on from `state` with `geodes` already open, updating `best`.
+    fn solve(&mut self, state: State, geodes: i32) {
+        self.visited += 1;
         let State {
             minutes_left,
             robots,
             resources,
             not_allowed_next,
-        } = state.clone();
+        } = state;
         if minutes_left == 0 {
-            return 0;
+            self.best = self.best.max(geodes);
+            return;
+        }
+        let upper_bound =
+            geodes + robots.geode_robots * minutes_left + minutes_left * (minutes_left - 1) / 2;
+        if upper_bound <= self.best {
+            return;
         }
 
         let robot_options = resources
@@ -338,8 +353,14 @@ impl Solver19 {
             .collect_vec();
         let (mined_resources, new_geodes) = robots.mine();
 
-        let mut best = 0;
-        for option in std::iter::once(None).chain(robot_options.iter().map(Some)) {
+        // Try the most valuable robots first, so that good plans are found
+        // early and prune more of the rest.
+        for option in robot_options
+            .iter()
+            .rev()
+            .map(Some)
+            .chain(std::iter::once(None))
+        {
             let option = option.copied();
             if let Some(option) = option {
                 if not_allowed_next.contains(&option) {
@@ -347,8 +368,8 @@ impl Solver19 {
                 }
             }
 
-            best = best.max(
-                self.solve(State {
+            self.solve(
+                State {
                     minutes_left: minutes_left - 1,
                     resources: resources
                         .spend(self.blueprint, option.map(Robots::from).unwrap_or_default())
@@ -362,46 +383,28 @@ impl Solver19 {
                             robot_options.iter().copied().collect()
                         }
                     },
-                }),
-            )
+                },
+                geodes + new_geodes,
+            );
         }
-
-        best + new_geodes
     }
 }
 
 #[aoc(day19, part1)]
 pub fn part1(input: &[Blueprint]) -> i32 {
-    // let input = &parse("Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
-    // Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.");
     input
         .iter()
         .enumerate()
         .map(|(i, &blueprint)| {
-            let mut solver = Solver19 {
-                blueprint,
-                cache: HashMap::new(),
-            };
-            let sol = solver.solve(State {
-                minutes_left: 24,
-                resources: Resources {
-                    ore: Ore(0),
-                    clay: Clay(0),
-                    obsidian: Obsidian(0),
-                },
-                robots: Robots {
-                    ore_robots: 1,
-                    clay_robots: 0,
-                    obsidian_robots: 0,
-                    geode_robots: 0,
-                },
-                not_allowed_next: BTreeSet::new(),
-            });
+            let mut solver = Solver19::new(blueprint);
+            let sol = solver.run(24);
 
             let i = i + 1;
-            println!(
-                "solved blueprint {i} with score {sol} and state size {}",
-                solver.cache.len()
+            debug!(
+                blueprint = i,
+                geodes = sol,
+                states = solver.visited,
+                "solved blueprint"
             );
             i32::try_from(i).unwrap() * sol
         })
@@ -410,37 +413,33 @@ pub fn part1(input: &[Blueprint]) -> i32 {
 
 #[aoc(day19, part2)]
 pub fn part2(input: &[Blueprint]) -> i32 {
-    // let input = &parse("Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
-    // Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.");
     input
         .iter()
         .take(3)
         .map(|&blueprint| {
-            let mut solver = Solver19 {
-                blueprint,
-                cache: HashMap::new(),
-            };
-            let sol = solver.solve(State {
-                minutes_left: 32,
-                resources: Resources {
-                    ore: Ore(0),
-                    clay: Clay(0),
-                    obsidian: Obsidian(0),
-                },
-                robots: Robots {
-                    ore_robots: 1,
-                    clay_robots: 0,
-                    obsidian_robots: 0,
-                    geode_robots: 0,
-                },
-                not_allowed_next: BTreeSet::new(),
-            });
+            let mut solver = Solver19::new(blueprint);
+            let sol = solver.run(32);
 
-            println!(
-                "solved blueprint with score {sol} and state size {}",
-                solver.cache.len()
-            );
+            debug!(geodes = sol, states = solver.visited, "solved blueprint");
             sol
         })
         .product()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 33);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 3472);
+    }
+}
@@ -1,11 +1,14 @@
 use std::{collections::BTreeMap, ops::Bound};
 
+use anyhow::Result;
 use itertools::Itertools;
 use num::{BigInt, BigRational};
 
+use crate::parsing::{self, number};
+
 #[aoc_generator(day20)]
-pub fn parse(input: &str) -> Vec<i64> {
-    input.lines().map(|s| s.parse().unwrap()).collect()
+pub fn parse(input: &str) -> Result<Vec<i64>> {
+    parsing::parse_lines(20, input, number)
 }
 
 fn bigrat(i: impl Into<BigInt>) -> BigRational {
@@ -14,15 +17,6 @@ fn bigrat(i: impl Into<BigInt>) -> BigRational {
 
 #[aoc(day20, part1)]
 pub fn part1(input: &[i64]) -> i64 {
-    let _input = &parse(
-        "1
-2
--3
-3
--2
-0
-4",
-    );
     let mut weight_to_num = input
         .iter()
         .enumerate()
@@ -56,30 +50,28 @@ pub fn part1(input: &[i64]) -> i64 {
         match result {
             Ok(lower_bound) => {
                 if let Some(upper_bound) = traversal.next() {
-                    assert!(matches!(
-                        weight_to_num.insert((lower_bound.0 + upper_bound.0) / bigrat(2), n),
-                        None
-                    ));
+                    assert!(weight_to_num
+                        .insert((lower_bound.0 + upper_bound.0) / bigrat(2), n)
+                        .is_none());
                 } else {
                     let new_k = lower_bound.0.clone() + bigrat(1);
-                    assert!(matches!(weight_to_num.insert(new_k, n), None));
+                    assert!(weight_to_num.insert(new_k, n).is_none());
                 }
             }
             Err(steps_remaining) => {
                 assert!(steps_remaining >= 1);
                 let lower_bound = weight_to_num.iter().nth(steps_remaining - 1).unwrap();
                 let upper_bound = weight_to_num.iter().nth(steps_remaining).unwrap();
-                assert!(matches!(
-                    weight_to_num.insert((lower_bound.0 + upper_bound.0) / bigrat(2), n),
-                    None
-                ));
+                assert!(weight_to_num
+                    .insert((lower_bound.0 + upper_bound.0) / bigrat(2), n)
+                    .is_none());
             }
         }
     }
     let zero_index = weight_to_num
         .iter()
         .enumerate()
-        .find_map(|(ind, (_, &x))| (x == 0).then(|| ind))
+        .find_map(|(ind, (_, &x))| (x == 0).then_some(ind))
         .unwrap();
     [1000, 2000, 3000]
         .into_iter()
@@ -94,15 +86,6 @@ pub fn part1(input: &[i64]) -> i64 {
 
 #[aoc(day20, part2)]
 pub fn part2(input: &[i64]) -> i64 {
-    let _input = &parse(
-        "1
-2
--3
-3
--2
-0
-4",
-    );
     let input = &input.iter().copied().map(|x| x * 811589153).collect_vec();
     let mut weight_to_num = input
         .iter()
@@ -141,11 +124,11 @@ pub fn part2(input: &[i64]) -> i64 {
                 Ok(lower_bound) => {
                     if let Some(upper_bound) = traversal.next() {
                         let new_weight = (lower_bound.0 + upper_bound.0) / bigrat(2);
-                        assert!(matches!(weight_to_num.insert(new_weight.clone(), n), None));
+                        assert!(weight_to_num.insert(new_weight.clone(), n).is_none());
                         weights[index] = new_weight;
                     } else {
                         let new_weight = lower_bound.0.clone() + bigrat(1);
-                        assert!(matches!(weight_to_num.insert(new_weight.clone(), n), None));
+                        assert!(weight_to_num.insert(new_weight.clone(), n).is_none());
                         weights[index] = new_weight.clone();
                     }
                 }
@@ -154,7 +137,7 @@ pub fn part2(input: &[i64]) -> i64 {
                     let lower_bound = weight_to_num.iter().nth(steps_remaining - 1).unwrap();
                     let upper_bound = weight_to_num.iter().nth(steps_remaining).unwrap();
                     let new_weight = (lower_bound.0 + upper_bound.0) / bigrat(2);
-                    assert!(matches!(weight_to_num.insert(new_weight.clone(), n), None));
+                    assert!(weight_to_num.insert(new_weight.clone(), n).is_none());
                     weights[index] = new_weight.clone();
                 }
             }
@@ -163,7 +146,7 @@ pub fn part2(input: &[i64]) -> i64 {
     let zero_index = weight_to_num
         .iter()
         .enumerate()
-        .find_map(|(ind, (_, &x))| (x == 0).then(|| ind))
+        .find_map(|(ind, (_, &x))| (x == 0).then_some(ind))
         .unwrap();
     [1000, 2000, 3000]
         .into_iter()
@@ -175,3 +158,26 @@ pub fn part2(input: &[i64]) -> i64 {
         })
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1
+2
+-3
+3
+-2
+0
+4";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 3);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 1623178306);
+    }
+}
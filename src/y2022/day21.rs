@@ -3,7 +3,11 @@ use std::{
     rc::Rc,
 };
 
+use anyhow::{bail, Result};
 use itertools::Itertools;
+use tracing::trace;
+
+use crate::parsing::{self, split_once};
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Op {
@@ -14,36 +18,31 @@ pub enum Op {
     Equ,
 }
 
+/// A monkey's job: either a number to yell, or an operation over two other monkeys.
+pub type Job = std::result::Result<Operand, (String, Op, String)>;
+
 #[aoc_generator(day21)]
-fn parse(input: &str) -> Vec<(String, Result<Operand, (String, Op, String)>)> {
-    input
-        .lines()
-        .map(|line| {
-            let line = line.trim();
-            let (root, exp) = line.split_once(": ").unwrap();
-            (
-                root.to_owned(),
-                (exp.parse::<i64>().map(Operand::Num).map_err(|_| {
-                    let (a, rest) = exp.split_once(" ").unwrap();
-                    let op = rest.chars().next().unwrap();
-                    let (_, b) = rest.split_once(" ").unwrap();
-                    (
-                        a.to_owned(),
-                        {
-                            match op {
-                                '+' => Op::Add,
-                                '-' => Op::Sub,
-                                '*' => Op::Mul,
-                                '/' => Op::Div,
-                                _ => panic!("{}", op),
-                            }
-                        },
-                        b.to_owned(),
-                    )
-                })),
-            )
-        })
-        .collect()
+pub fn parse(input: &str) -> Result<Vec<(String, Job)>> {
+    parsing::parse_lines(21, input, |line| {
+        let line = line.trim();
+        let (root, exp) = split_once(line, ": ")?;
+        let job = match exp.parse::<i64>() {
+            Ok(n) => Ok(Operand::Num(n)),
+            Err(_) => {
+                let (a, rest) = split_once(exp, " ")?;
+                let (op, b) = split_once(rest, " ")?;
+                let op = match op {
+                    "+" => Op::Add,
+                    "-" => Op::Sub,
+                    "*" => Op::Mul,
+                    "/" => Op::Div,
+                    _ => bail!("unknown operator {op:?}"),
+                };
+                Err((a.to_owned(), op, b.to_owned()))
+            }
+        };
+        Ok((root.to_owned(), job))
+    })
 }
 
 #[derive(Clone)]
@@ -62,28 +61,7 @@ impl Operand {
 }
 
 #[aoc(day21, part1)]
-fn part1(input: &[(String, Result<Operand, (String, Op, String)>)]) -> i64 {
-    let _input = &parse(
-        "root: pppw + sjmn
-  dbpl: 5
-  cczh: sllz + lgvd
-  zczc: 2
-  ptdq: humn - dvpt
-  dvpt: 3
-  lfqf: 4
-  humn: 5
-  ljgn: 2
-  sjmn: drzm * dbpl
-  sllz: 4
-  pppw: cczh / lfqf
-  lgvd: ljgn * ptdq
-  drzm: hmdt - zczc
-  hmdt: 32",
-    );
-    part1_impl(input)
-}
-
-fn part1_impl(input: &[(String, Result<Operand, (String, Op, String)>)]) -> i64 {
+pub fn part1(input: &[(String, Job)]) -> i64 {
     let mut map = HashMap::new();
     let mut rev_refs = HashMap::new();
     let mut resolved = HashMap::new();
@@ -107,7 +85,7 @@ fn part1_impl(input: &[(String, Result<Operand, (String, Op, String)>)]) -> i64
     }
     let mut newly_resolved = resolved.keys().cloned().collect_vec();
     while !newly_resolved.is_empty() {
-        for monkey in std::mem::replace(&mut newly_resolved, Vec::new()) {
+        for monkey in std::mem::take(&mut newly_resolved) {
             for &rev_ref in rev_refs.get(&monkey).unwrap_or(&BTreeSet::new()) {
                 if resolved.contains_key(rev_ref) {
                     continue;
@@ -134,7 +112,7 @@ fn part1_impl(input: &[(String, Result<Operand, (String, Op, String)>)]) -> i64
 }
 
 #[aoc(day21, part2)]
-fn part2(input: &[(String, Result<Operand, (String, Op, String)>)]) -> i64 {
+pub fn part2(input: &[(String, Job)]) -> i64 {
     let mut input = input.to_vec();
     for (monkey, job) in input.iter_mut() {
         if monkey == "root" {
@@ -169,7 +147,7 @@ fn part2(input: &[(String, Result<Operand, (String, Op, String)>)]) -> i64 {
     }
     let mut newly_resolved = resolved.keys().cloned().collect_vec();
     while !newly_resolved.is_empty() {
-        for monkey in std::mem::replace(&mut newly_resolved, Vec::new()) {
+        for monkey in std::mem::take(&mut newly_resolved) {
             for &rev_ref in rev_refs.get(&monkey).unwrap_or(&BTreeSet::new()) {
                 if resolved.contains_key(rev_ref) {
                     continue;
@@ -204,7 +182,7 @@ fn part2(input: &[(String, Result<Operand, (String, Op, String)>)]) -> i64 {
                             Op::Mul => Rc::new(move |n| f(n / b.num().unwrap())),
                             Op::Div => Rc::new(move |n| f(n * b.num().unwrap())),
                             Op::Equ => {
-                                println!("hit equ");
+                                trace!(monkey = %rev_ref, "skipping the equality at root");
                                 continue;
                             }
                         }),
@@ -221,7 +199,7 @@ fn part2(input: &[(String, Result<Operand, (String, Op, String)>)]) -> i64 {
                             Op::Mul => Rc::new(move |n| f(n / a.num().unwrap())),
                             Op::Div => Rc::new(move |n| f(a.num().unwrap() / n)),
                             Op::Equ => {
-                                println!("hit equ");
+                                trace!(monkey = %rev_ref, "skipping the equality at root");
                                 continue;
                             }
                         }),
@@ -240,3 +218,34 @@ fn part2(input: &[(String, Result<Operand, (String, Op, String)>)]) -> i64 {
     };
     f(x)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 152);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 301);
+    }
+}
@@ -0,0 +1,429 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::{
+    grid::Grid,
+    parsing::{self, number},
+    point::Point3,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Tile {
+    /// Off the board: the blank space around the net.
+    Void,
+    Open,
+    Wall,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Step {
+    Forward(usize),
+    Left,
+    Right,
+}
+
+/// The board and the path to walk on it.
+#[derive(Debug)]
+pub struct Notes {
+    pub map: Grid<Tile>,
+    pub path: Vec<Step>,
+}
+
+/// Facings in clockwise order, numbered the way the password scores them.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Facing {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Facing {
+    const CLOCKWISE: [Facing; 4] = [Facing::Right, Facing::Down, Facing::Left, Facing::Up];
+
+    fn delta(self) -> (i64, i64) {
+        match self {
+            Facing::Right => (0, 1),
+            Facing::Down => (1, 0),
+            Facing::Left => (0, -1),
+            Facing::Up => (-1, 0),
+        }
+    }
+
+    fn turned(self, quarter_turns: usize) -> Facing {
+        Facing::CLOCKWISE[(self as usize + quarter_turns) % 4]
+    }
+}
+
+#[aoc_generator(day22)]
+pub fn parse(input: &str) -> Result<Notes> {
+    let mut lines = parsing::lines(22, input);
+    let board = lines
+        .by_ref()
+        .take_while(|line| !line.text.trim().is_empty())
+        .collect::<Vec<_>>();
+    let path = lines
+        .next()
+        .ok_or_else(|| anyhow!("day 22: expected a board and a path separated by a blank line"))?;
+    if let Some(extra) = lines.next() {
+        return Err(extra.error(anyhow!("expected the path to be the last line")));
+    }
+
+    let width = board.iter().map(|line| line.text.len()).max().unwrap_or(0);
+    let rows = board
+        .iter()
+        .map(|line| {
+            line.parse(|text| {
+                let mut row = text
+                    .chars()
+                    .map(|c| match c {
+                        ' ' => Ok(Tile::Void),
+                        '.' => Ok(Tile::Open),
+                        '#' => Ok(Tile::Wall),
+                        _ => bail!("unexpected tile {c:?}"),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                row.resize(width, Tile::Void);
+                Ok(row)
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let map = Grid::from_rows(rows)?;
+    if !map.iter().any(|(_, &tile)| tile == Tile::Open) {
+        bail!("day 22: the board has no open tiles");
+    }
+
+    let path = path.parse(|text| {
+        let mut steps = Vec::new();
+        let mut digits = String::new();
+        for c in text.trim_end().chars() {
+            if c.is_ascii_digit() {
+                digits.push(c);
+                continue;
+            }
+            if !digits.is_empty() {
+                steps.push(Step::Forward(number(&std::mem::take(&mut digits))?));
+            }
+            steps.push(match c {
+                'L' => Step::Left,
+                'R' => Step::Right,
+                _ => bail!("unexpected step {c:?}"),
+            });
+        }
+        if !digits.is_empty() {
+            steps.push(Step::Forward(number(&digits)?));
+        }
+        Ok(steps)
+    })?;
+    Ok(Notes { map, path })
+}
+
+/// Follows the path from the leftmost open tile of the top row, calling
+/// `wrap` to find where a step off the board lands and which way it faces.
+/// Returns the password for the final position.
+fn walk(notes: &Notes, wrap: impl Fn((usize, usize), Facing) -> ((usize, usize), Facing)) -> usize {
+    let map = &notes.map;
+    let start_col = (0..map.width())
+        .find(|&col| map[(0, col)] == Tile::Open)
+        .unwrap();
+    let mut pos = (0, start_col);
+    let mut facing = Facing::Right;
+    for &step in &notes.path {
+        match step {
+            Step::Left => facing = facing.turned(3),
+            Step::Right => facing = facing.turned(1),
+            Step::Forward(count) => {
+                for _ in 0..count {
+                    let (next, next_facing) = match map
+                        .offset(pos, facing.delta())
+                        .filter(|&next| map[next] != Tile::Void)
+                    {
+                        Some(next) => (next, facing),
+                        None => wrap(pos, facing),
+                    };
+                    if map[next] == Tile::Wall {
+                        break;
+                    }
+                    (pos, facing) = (next, next_facing);
+                }
+            }
+        }
+    }
+    1000 * (pos.0 + 1) + 4 * (pos.1 + 1) + facing as usize
+}
+
+#[aoc(day22, part1)]
+pub fn part1(input: &Notes) -> usize {
+    let map = &input.map;
+    walk(input, |mut pos, facing| {
+        let (d_row, d_col) = facing.delta();
+        while let Some(prev) = map
+            .offset(pos, (-d_row, -d_col))
+            .filter(|&prev| map[prev] != Tile::Void)
+        {
+            pos = prev;
+        }
+        (pos, facing)
+    })
+}
+
+/// How one face of the net sits on the folded cube: the 3D directions its
+/// rightward and downward axes point in, and its outward normal.
+#[derive(Clone, Copy, Debug)]
+struct Frame {
+    right: Point3<i32>,
+    down: Point3<i32>,
+    normal: Point3<i32>,
+}
+
+impl Frame {
+    /// The frame of the face one step in `facing` along the net, folded over
+    /// the shared edge.
+    fn neighbor(self, facing: Facing) -> Frame {
+        let Frame {
+            right,
+            down,
+            normal,
+        } = self;
+        match facing {
+            Facing::Right => Frame {
+                right: -normal,
+                down,
+                normal: right,
+            },
+            Facing::Down => Frame {
+                right,
+                down: -normal,
+                normal: down,
+            },
+            Facing::Left => Frame {
+                right: normal,
+                down,
+                normal: -right,
+            },
+            Facing::Up => Frame {
+                right,
+                down: normal,
+                normal: -down,
+            },
+        }
+    }
+
+    /// The 3D direction of travel when facing `facing` on this face.
+    fn direction(self, facing: Facing) -> Point3<i32> {
+        match facing {
+            Facing::Right => self.right,
+            Facing::Down => self.down,
+            Facing::Left => -self.right,
+            Facing::Up => -self.down,
+        }
+    }
+}
+
+/// The net folded into a cube, keyed by each face's `(row, col)` among the
+/// `size`-by-`size` tiles of the board.
+struct Cube {
+    size: usize,
+    faces: HashMap<(usize, usize), Frame>,
+}
+
+impl Cube {
+    fn fold(map: &Grid<Tile>) -> Result<Cube> {
+        let area = map.iter().filter(|(_, &tile)| tile != Tile::Void).count();
+        let size = (1..=area).find(|size| 6 * size * size >= area).unwrap_or(0);
+        if size == 0 || 6 * size * size != area {
+            bail!("day 22: {area} tiles can't be the six faces of a cube");
+        }
+
+        let is_face = |(row, col): (usize, usize)| {
+            map.get((row * size, col * size))
+                .is_some_and(|&tile| tile != Tile::Void)
+        };
+        let first = (0..map.width() / size)
+            .map(|col| (0, col))
+            .find(|&face| is_face(face))
+            .unwrap();
+        let mut faces = HashMap::from([(
+            first,
+            Frame {
+                right: Point3::UNIT_X,
+                down: Point3::UNIT_Y,
+                normal: -Point3::UNIT_Z,
+            },
+        )]);
+        let mut queue = VecDeque::from([first]);
+        while let Some(face) = queue.pop_front() {
+            let frame = faces[&face];
+            for facing in Facing::CLOCKWISE {
+                let (d_row, d_col) = facing.delta();
+                let (Ok(row), Ok(col)) = (
+                    usize::try_from(face.0 as i64 + d_row),
+                    usize::try_from(face.1 as i64 + d_col),
+                ) else {
+                    continue;
+                };
+                if is_face((row, col)) && !faces.contains_key(&(row, col)) {
+                    faces.insert((row, col), frame.neighbor(facing));
+                    queue.push_back((row, col));
+                }
+            }
+        }
+        let on_a_face = |((row, col), &tile): ((usize, usize), &Tile)| {
+            tile == Tile::Void || faces.contains_key(&(row / size, col / size))
+        };
+        let normals = faces
+            .values()
+            .map(|frame| frame.normal)
+            .collect::<HashSet<_>>();
+        if faces.len() != 6 || normals.len() != 6 || !map.iter().all(on_a_face) {
+            bail!("day 22: the board is not the net of a cube");
+        }
+        Ok(Cube { size, faces })
+    }
+
+    /// Where stepping off the edge of a face at `pos` towards `facing` lands.
+    fn wrap(&self, (row, col): (usize, usize), facing: Facing) -> ((usize, usize), Facing) {
+        let size = self.size;
+        let from = self.faces[&(row / size, col / size)];
+        let travel = from.direction(facing);
+        let (&(to_row, to_col), &to) = self
+            .faces
+            .iter()
+            .find(|(_, frame)| frame.normal == travel)
+            .unwrap();
+
+        // Over the edge, we keep going into the cube, away from the old face's normal.
+        let heading = -from.normal;
+        let new_facing = Facing::CLOCKWISE
+            .into_iter()
+            .find(|&facing| to.direction(facing) == heading)
+            .unwrap();
+
+        // The shared edge runs along the same 3D axis on both faces, so measure
+        // the position along it the same way on each side.
+        let (edge, along) = match facing {
+            Facing::Right | Facing::Left => (from.down, row % size),
+            Facing::Up | Facing::Down => (from.right, col % size),
+        };
+        let across = |axis: Point3<i32>| {
+            if axis == edge {
+                along
+            } else {
+                size - 1 - along
+            }
+        };
+        let (r, c) = match new_facing {
+            Facing::Right => (across(to.down), 0),
+            Facing::Left => (across(to.down), size - 1),
+            Facing::Down => (0, across(to.right)),
+            Facing::Up => (size - 1, across(to.right)),
+        };
+        ((to_row * size + r, to_col * size + c), new_facing)
+    }
+}
+
+#[aoc(day22, part2)]
+pub fn part2(input: &Notes) -> Result<usize> {
+    let cube = Cube::fold(&input.map)?;
+    Ok(walk(input, |pos, facing| cube.wrap(pos, facing)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..#.......#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 6032);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()).unwrap(), 5031);
+    }
+
+    #[test]
+    fn crlf_input() {
+        let notes = parse(&EXAMPLE.replace('\n', "\r\n")).unwrap();
+        assert_eq!(part1(&notes), 6032);
+        assert_eq!(part2(&notes).unwrap(), 5031);
+    }
+
+    #[test]
+    fn boards_that_dont_fold() {
+        let notes = parse("...\n.#.\n\n2R1").unwrap();
+        assert_eq!(part1(&notes), 2000 + 4 * 3 + 1);
+        assert!(part2(&notes).is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("..\n.x\n\n1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 22, line 2 (\".x\"): unexpected tile 'x'"
+        );
+        let err = parse("..\n..\n\n1R2F").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 22, line 4 (\"1R2F\"): unexpected step 'F'"
+        );
+        assert!(parse("..\n..").is_err());
+    }
+
+    /// Stepping off an edge and straight back again returns to where we were,
+    /// on the example's net and on the differently shaped net of the real input.
+    #[test]
+    fn cube_wrap_round_trips() {
+        let other_net = "  ....
+  ....
+  ..
+  ..
+....
+....
+..
+..
+
+1";
+        for input in [EXAMPLE, other_net] {
+            let map = parse(input).unwrap().map;
+            let cube = Cube::fold(&map).unwrap();
+            for (pos, &tile) in map.iter() {
+                if tile == Tile::Void {
+                    continue;
+                }
+                for facing in Facing::CLOCKWISE {
+                    if map
+                        .offset(pos, facing.delta())
+                        .is_some_and(|next| map[next] != Tile::Void)
+                    {
+                        continue;
+                    }
+                    let (there, arrived) = cube.wrap(pos, facing);
+                    assert_ne!(map[there], Tile::Void);
+                    assert_eq!(
+                        cube.wrap(there, arrived.turned(2)),
+                        (pos, facing.turned(2)),
+                        "stepping {facing:?} from {pos:?}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{bail, Result};
+
+use crate::grid::SparseGrid;
+
+type Offset = (i64, i64);
+
+/// The direction each proposal moves in, with the three cells on that side
+/// that have to be empty, in the order they are considered in the first round.
+const PROPOSALS: [(Offset, [Offset; 3]); 4] = [
+    ((0, -1), [(-1, -1), (0, -1), (1, -1)]),
+    ((0, 1), [(-1, 1), (0, 1), (1, 1)]),
+    ((-1, 0), [(-1, -1), (-1, 0), (-1, 1)]),
+    ((1, 0), [(1, -1), (1, 0), (1, 1)]),
+];
+
+#[aoc_generator(day23)]
+pub fn parse(input: &str) -> Result<SparseGrid<()>> {
+    SparseGrid::parse(23, input, |c| match c {
+        '#' => Ok(Some(())),
+        '.' => Ok(None),
+        _ => bail!("unexpected tile {c:?}"),
+    })
+}
+
+/// Plays one round, starting with proposal number `round % 4`. Returns
+/// whether any elf moved.
+fn play_round(elves: &mut HashSet<(i64, i64)>, round: usize) -> bool {
+    let occupied = |(x, y): (i64, i64), (dx, dy): (i64, i64)| elves.contains(&(x + dx, y + dy));
+
+    let mut proposed: HashMap<(i64, i64), Vec<(i64, i64)>> = HashMap::new();
+    for &elf in elves.iter() {
+        if !SparseGrid::<()>::neighbors8(elf).any(|pos| elves.contains(&pos)) {
+            continue;
+        }
+        let proposal = (0..4)
+            .map(|i| PROPOSALS[(round + i) % 4])
+            .find(|(_, checks)| checks.iter().all(|&delta| !occupied(elf, delta)));
+        if let Some(((dx, dy), _)) = proposal {
+            proposed
+                .entry((elf.0 + dx, elf.1 + dy))
+                .or_default()
+                .push(elf);
+        }
+    }
+
+    let mut moved = false;
+    for (target, candidates) in proposed {
+        if let [elf] = candidates[..] {
+            elves.remove(&elf);
+            elves.insert(target);
+            moved = true;
+        }
+    }
+    moved
+}
+
+#[aoc(day23, part1)]
+pub fn part1(input: &SparseGrid<()>) -> i64 {
+    let mut elves: HashSet<(i64, i64)> = input.positions().collect();
+    for round in 0..10 {
+        play_round(&mut elves, round);
+    }
+    let grid: SparseGrid<()> = elves.into_iter().map(|pos| (pos, ())).collect();
+    let Some(((min_x, min_y), (max_x, max_y))) = grid.bounds() else {
+        return 0;
+    };
+    (max_x - min_x + 1) * (max_y - min_y + 1) - grid.len() as i64
+}
+
+#[aoc(day23, part2)]
+pub fn part2(input: &SparseGrid<()>) -> usize {
+    let mut elves: HashSet<(i64, i64)> = input.positions().collect();
+    (0..).find(|&round| !play_round(&mut elves, round)).unwrap() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 110);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 20);
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::grid::Grid;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Tile {
+    Wall,
+    Open,
+    /// A blizzard as it stands at minute 0, moving by `(d_row, d_col)` each minute.
+    Blizzard(i64, i64),
+}
+
+/// The valley walls, with the blizzards and the gaps in the top and bottom walls.
+pub struct Valley {
+    pub map: Grid<Tile>,
+    pub start: (usize, usize),
+    pub goal: (usize, usize),
+}
+
+impl Valley {
+    /// Whether a blizzard covers `(row, col)` at `minute`. Blizzards wrap
+    /// around inside the walls, so look back along each direction for one
+    /// that started `minute` steps upwind.
+    fn blizzard_at(&self, (row, col): (usize, usize), minute: usize) -> bool {
+        let inner_height = (self.map.height() - 2) as i64;
+        let inner_width = (self.map.width() - 2) as i64;
+        let (row, col) = (row as i64 - 1, col as i64 - 1);
+        let minute = minute as i64;
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .any(|(d_row, d_col)| {
+                let source = (
+                    (row - d_row * minute).rem_euclid(inner_height) as usize + 1,
+                    (col - d_col * minute).rem_euclid(inner_width) as usize + 1,
+                );
+                self.map[source] == Tile::Blizzard(d_row, d_col)
+            })
+    }
+
+    fn is_free(&self, pos: (usize, usize), minute: usize) -> bool {
+        if pos == self.start || pos == self.goal {
+            return true;
+        }
+        self.map[pos] != Tile::Wall && !self.blizzard_at(pos, minute)
+    }
+
+    /// How many minutes the blizzards take to return to where they started.
+    fn period(&self) -> usize {
+        num::integer::lcm(self.map.height() - 2, self.map.width() - 2)
+    }
+
+    /// The first minute, after `minute`, when `to` can be reached from `from`,
+    /// or an error if the valley has no way through.
+    fn crossing(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+        mut minute: usize,
+    ) -> Result<usize> {
+        let period = self.period();
+        // Waiting at `from` is always safe and the blizzards repeat every
+        // `period` minutes, so the reachable set never shrinks from one period
+        // to the next. Once it stops growing it will never reach `to`.
+        let mut sizes = vec![0; period];
+        let mut reachable = HashSet::from([from]);
+        while !reachable.contains(&to) {
+            minute += 1;
+            reachable = reachable
+                .iter()
+                .flat_map(|&pos| std::iter::once(pos).chain(self.map.neighbors4(pos)))
+                .filter(|&pos| self.is_free(pos, minute))
+                .collect();
+            let size = &mut sizes[minute % period];
+            if *size == reachable.len() {
+                bail!("day 24: no way across the valley from {from:?} to {to:?}");
+            }
+            *size = reachable.len();
+        }
+        Ok(minute)
+    }
+}
+
+#[aoc_generator(day24)]
+pub fn parse(input: &str) -> Result<Valley> {
+    let map = Grid::parse(24, input, |c| {
+        Ok(match c {
+            '#' => Tile::Wall,
+            '.' => Tile::Open,
+            '^' => Tile::Blizzard(-1, 0),
+            'v' => Tile::Blizzard(1, 0),
+            '<' => Tile::Blizzard(0, -1),
+            '>' => Tile::Blizzard(0, 1),
+            _ => bail!("unexpected tile {c:?}"),
+        })
+    })?;
+    if map.height() < 3 || map.width() < 3 {
+        bail!("day 24: the valley needs walls around at least one open tile");
+    }
+    let gap = |row: usize| {
+        (0..map.width())
+            .find(|&col| map[(row, col)] == Tile::Open)
+            .map(|col| (row, col))
+            .ok_or_else(|| anyhow!("day 24: no gap in the wall on row {}", row + 1))
+    };
+    let start = gap(0)?;
+    let goal = gap(map.height() - 1)?;
+    Ok(Valley { map, start, goal })
+}
+
+#[aoc(day24, part1)]
+pub fn part1(input: &Valley) -> Result<usize> {
+    input.crossing(input.start, input.goal, 0)
+}
+
+#[aoc(day24, part2)]
+pub fn part2(input: &Valley) -> Result<usize> {
+    let there = input.crossing(input.start, input.goal, 0)?;
+    let back = input.crossing(input.goal, input.start, there)?;
+    input.crossing(input.start, input.goal, back)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()).unwrap(), 18);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()).unwrap(), 54);
+    }
+
+    #[test]
+    fn blocked_valleys() {
+        // A wall across the valley.
+        assert!(part1(&parse("#.####\n#.#..#\n####.#").unwrap()).is_err());
+        // A blizzard that never leaves the only open tile.
+        let err = part1(&parse("#.#\n#v#\n#.#").unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 24: no way across the valley from (0, 1) to (2, 1)"
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use anyhow::{bail, Result};
+
+use crate::parsing;
+
+/// Decodes a SNAFU number: base 5 with digits `=`, `-`, `0`, `1` and `2`
+/// standing for -2 to 2.
+pub fn from_snafu(s: &str) -> Result<i64> {
+    if s.is_empty() {
+        bail!("empty SNAFU number");
+    }
+    s.chars().try_fold(0i64, |n, c| {
+        let digit = match c {
+            '=' => -2,
+            '-' => -1,
+            '0' => 0,
+            '1' => 1,
+            '2' => 2,
+            _ => bail!("invalid SNAFU digit {c:?}"),
+        };
+        Ok(n * 5 + digit)
+    })
+}
+
+pub fn to_snafu(mut n: i64) -> String {
+    if n == 0 {
+        return "0".to_owned();
+    }
+    let mut digits = Vec::new();
+    while n != 0 {
+        // Shift each digit from 0..5 to -2..3 and carry the difference.
+        let digit = (n + 2).rem_euclid(5) - 2;
+        digits.push(match digit {
+            -2 => '=',
+            -1 => '-',
+            0 => '0',
+            1 => '1',
+            _ => '2',
+        });
+        n = (n - digit) / 5;
+    }
+    digits.iter().rev().collect()
+}
+
+#[aoc_generator(day25)]
+pub fn parse(input: &str) -> Result<Vec<i64>> {
+    parsing::parse_lines(25, input, from_snafu)
+}
+
+#[aoc(day25, part1)]
+pub fn part1(input: &[i64]) -> String {
+    to_snafu(input.iter().sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(parse(EXAMPLE).unwrap().iter().sum::<i64>(), 4890);
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), "2=-1=0");
+    }
+
+    #[test]
+    fn snafu_round_trip() {
+        for (n, snafu) in [
+            (0, "0"),
+            (3, "1="),
+            (8, "2="),
+            (2022, "1=11-2"),
+            (314159265, "1121-1110-1=0"),
+        ] {
+            assert_eq!(to_snafu(n), snafu);
+            assert_eq!(from_snafu(snafu).unwrap(), n);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Advent of Code 2022. These days carry the `#[aoc]` attributes that
+//! `cargo aoc` runs.
+
+use crate::solutions::{day, Day};
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+pub const DAYS: &[Day] = &[
+    day!(1, day01, pt1, pt2),
+    day!(2, day02, pt1, pt2),
+    day!(3, day03, pt1, pt2),
+    day!(4, day04, pt1, pt2),
+    day!(5, day05, pt1, pt2),
+    day!(6, day06, pt1, pt2),
+    day!(7, day07, pt1, pt2),
+    day!(8, day08, part1, part2),
+    day!(9, day09, part1, part2),
+    day!(10, day10, part1, part2),
+    day!(11, day11, part1, part2),
+    day!(12, day12, part1, part2),
+    day!(13, day13, part1, part2),
+    day!(14, day14, part1, part2),
+    day!(15, day15, part1, part2),
+    day!(16, day16, part1, part2),
+    day!(17, day17, part1, part2),
+    day!(18, day18, part1, part2),
+    day!(19, day19, part1, part2),
+    day!(20, day20, part1, part2),
+    day!(21, day21, part1, part2),
+    day!(22, day22, part1, part2),
+    day!(23, day23, part1, part2),
+    day!(24, day24, part1, part2),
+    day!(25, day25, part1),
+];
@@ -0,0 +1,79 @@
+//! Runs every registered day on its real input and compares the results with
+//! `answers/<year>.toml`.
+//!
+//! This takes minutes even with optimizations, so it only runs on request:
+//!
+//! ```text
+//! cargo test --release --test answers -- --ignored --nocapture
+//! ```
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use aoc2022::solutions::{self, YEARS};
+use serde::Deserialize;
+
+#[derive(Deserialize, Default)]
+struct Expected {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+#[test]
+#[ignore = "solves every day on the full input; run with --release -- --ignored"]
+fn answers_match_recorded() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut failures = Vec::new();
+    for &year in YEARS {
+        let Ok(answers) = fs::read_to_string(root.join(format!("answers/{year}.toml"))) else {
+            println!("{year}: no recorded answers, skipping");
+            continue;
+        };
+        let answers: BTreeMap<String, Expected> = toml::from_str(&answers).unwrap();
+        for day in solutions::days(year).unwrap() {
+            let name = format!("{year} day{}", day.day);
+            let Some(expected) = answers.get(&format!("day{}", day.day)) else {
+                println!("{name}: no recorded answers, skipping");
+                continue;
+            };
+            let path = root.join(format!("input/{year}/day{}.txt", day.day));
+            let Ok(input) = fs::read_to_string(path) else {
+                println!("{name}: no input, skipping");
+                continue;
+            };
+            let solvers = match (day.parse)(input.trim_end_matches('\n')) {
+                Ok(solvers) => solvers,
+                Err(err) => {
+                    failures.push(format!("{name}: failed to parse: {err:#}"));
+                    continue;
+                }
+            };
+            for (part, (solver, expected)) in solvers
+                .iter()
+                .zip([&expected.part1, &expected.part2])
+                .enumerate()
+            {
+                let part = part + 1;
+                let Some(expected) = expected else {
+                    println!("{name} part {part}: no recorded answer, skipping");
+                    continue;
+                };
+                let actual = match solver() {
+                    Ok(actual) => actual,
+                    Err(err) => {
+                        failures.push(format!("{name} part {part}: failed: {err:#}"));
+                        continue;
+                    }
+                };
+                // Multi-line answers are stored without the leading newline.
+                if actual.trim() == expected.trim() {
+                    println!("{name} part {part}: ok");
+                } else {
+                    failures.push(format!(
+                        "{name} part {part}: expected {expected:?}, got {actual:?}"
+                    ));
+                }
+            }
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}